@@ -1,14 +1,20 @@
+use crate::config::Permission;
+
 #[derive(Debug, Clone)]
 pub struct TwitchMessage {
-    #[allow(dead_code)]
     pub username: String,
+    pub user_id: String,
+    pub message_id: String,
     pub message: String,
-    #[allow(dead_code)]
     pub channel: String,
     pub is_moderator: bool,
     pub is_broadcaster: bool,
     pub is_vip: bool,
+    pub is_subscriber: bool,
     pub custom_reward_id: Option<String>,
+    pub emote_count: usize,
+    pub is_first_message: bool,
+    pub is_returning_chatter: bool,
 }
 
 impl TwitchMessage {
@@ -19,7 +25,13 @@ impl TwitchMessage {
 
         let mut is_moderator = false;
         let mut is_vip = false;
+        let mut is_subscriber = false;
         let mut custom_reward_id = None;
+        let mut user_id = String::new();
+        let mut message_id = String::new();
+        let mut emote_count = 0;
+        let mut is_first_message = false;
+        let mut is_returning_chatter = false;
 
         let message_without_tags = if raw_message.starts_with('@') {
             let space_pos = raw_message.find(' ')?;
@@ -32,9 +44,21 @@ impl TwitchMessage {
                             is_moderator = value.contains("moderator/");
                             is_vip = value.contains("vip/");
                         }
+                        "subscriber" => is_subscriber = value == "1",
                         "custom-reward-id" if !value.is_empty() => {
                             custom_reward_id = Some(value.to_string());
                         }
+                        "user-id" => user_id = value.to_string(),
+                        "id" => message_id = value.to_string(),
+                        "first-msg" => is_first_message = value == "1",
+                        "returning-chatter" => is_returning_chatter = value == "1",
+                        "emotes" if !value.is_empty() => {
+                            emote_count = value
+                                .split('/')
+                                .filter_map(|emote| emote.split_once(':'))
+                                .map(|(_, positions)| positions.split(',').count())
+                                .sum();
+                        }
                         _ => {}
                     }
                 }
@@ -66,16 +90,75 @@ impl TwitchMessage {
 
         Some(Self {
             username,
+            user_id,
+            message_id,
             message,
             channel: parsed_channel,
             is_moderator,
             is_broadcaster,
             is_vip,
+            is_subscriber,
             custom_reward_id,
+            emote_count,
+            is_first_message,
+            is_returning_chatter,
         })
     }
 
+    pub fn arg_text(&self) -> &str {
+        self.message
+            .trim()
+            .split_once(char::is_whitespace)
+            .map(|(_, rest)| rest.trim())
+            .unwrap_or("")
+    }
+
     pub fn has_permissions(&self) -> bool {
         self.is_moderator || self.is_vip || self.is_broadcaster
     }
+
+    pub fn permission(&self) -> Permission {
+        if self.is_broadcaster {
+            Permission::Broadcaster
+        } else if self.is_moderator {
+            Permission::Moderator
+        } else if self.is_vip {
+            Permission::Vip
+        } else if self.is_subscriber {
+            Permission::Subscriber
+        } else {
+            Permission::Everyone
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Membership {
+    Join(String),
+    Part(String),
+}
+
+impl Membership {
+    pub fn parse(raw_message: &str, channel: &str) -> Option<Self> {
+        let parts: Vec<&str> = raw_message.split_whitespace().collect();
+        let [prefix, command, target, ..] = parts.as_slice() else {
+            return None;
+        };
+
+        if !target.trim_start_matches('#').eq_ignore_ascii_case(channel) {
+            return None;
+        }
+
+        let login = prefix
+            .trim_start_matches(':')
+            .split('!')
+            .next()?
+            .to_lowercase();
+
+        match *command {
+            "JOIN" => Some(Self::Join(login)),
+            "PART" => Some(Self::Part(login)),
+            _ => None,
+        }
+    }
 }
@@ -3,17 +3,21 @@ pub mod message;
 use std::sync::Arc;
 
 use futures_util::{SinkExt, StreamExt};
-pub use message::TwitchMessage;
-use tokio::net::TcpStream;
+pub use message::{Membership, TwitchMessage};
+use tokio::{net::TcpStream, sync::mpsc};
 use tokio_tungstenite::{
     MaybeTlsStream, WebSocketStream, connect_async, tungstenite::protocol::Message,
 };
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    commands::CommandRegistry,
+    commands::{CommandRegistry, Invocation},
     config::Config,
     error::{BotError, Result},
+    eventsub::{EventSubClient, EventSubEvent},
+    helix::{HelixClient, RedemptionStatus},
+    moderation::Verdict,
+    storage,
 };
 
 type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
@@ -22,21 +26,53 @@ pub struct TwitchBot {
     config: Arc<Config>,
     websocket: Option<WebSocket>,
     commands: CommandRegistry,
+    helix: HelixClient,
+    event_sender: mpsc::UnboundedSender<EventSubEvent>,
+    events: mpsc::UnboundedReceiver<EventSubEvent>,
+    outbox_sender: mpsc::UnboundedSender<String>,
+    outbox: mpsc::UnboundedReceiver<String>,
 }
 
 impl TwitchBot {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config) -> Result<Self> {
         let config = Arc::new(config);
-        let commands = CommandRegistry::new(Arc::clone(&config));
+        let helix = HelixClient::new(Arc::clone(&config));
+        let storage = storage::open(&config)?;
+        let commands = CommandRegistry::new(Arc::clone(&config), helix.clone(), storage);
+        let (event_sender, events) = mpsc::unbounded_channel();
+        let (outbox_sender, outbox) = mpsc::unbounded_channel();
 
-        Self {
+        Ok(Self {
             config,
             websocket: None,
             commands,
-        }
+            helix,
+            event_sender,
+            events,
+            outbox_sender,
+            outbox,
+        })
     }
 
     pub async fn connect(&mut self) -> Result<()> {
+        if self.helix.is_configured() {
+            if let Err(e) = self.helix.validate().await {
+                error!("Helix token validation failed: {}", e);
+            }
+            self.helix.spawn_validation();
+        } else {
+            warn!("Helix credentials not configured");
+        }
+
+        if self.config.eventsub.enabled {
+            EventSubClient::new(
+                Arc::clone(&self.config),
+                self.helix.clone(),
+                self.event_sender.clone(),
+            )
+            .spawn();
+        }
+
         info!("Connecting to Twitch IRC");
 
         let (ws_stream, _) = connect_async("wss://irc-ws.chat.twitch.tv:443").await?;
@@ -51,6 +87,8 @@ impl TwitchBot {
         self.send_raw(&format!("JOIN #{}", self.config.twitch.channel))
             .await?;
 
+        self.commands.start_timers(self.outbox_sender.clone());
+
         info!("Connected to channel: #{}", self.config.twitch.channel);
         Ok(())
     }
@@ -73,25 +111,78 @@ impl TwitchBot {
 
     pub async fn run(&mut self) -> Result<()> {
         while let Some(ws) = &mut self.websocket {
-            match ws.next().await {
-                Some(Ok(Message::Text(text))) => {
-                    if let Err(e) = self.handle_message(&text).await {
-                        error!("Error handling message: {}", e);
+            tokio::select! {
+                frame = ws.next() => match frame {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Err(e) = self.handle_message(&text).await {
+                            error!("Error handling message: {}", e);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) => {
+                        warn!("WebSocket connection closed");
+                        break;
+                    }
+                    Some(Err(e)) => {
+                        error!("WebSocket error: {}", e);
+                        break;
+                    }
+                    None => {
+                        warn!("WebSocket stream ended");
+                        break;
+                    }
+                    _ => {}
+                },
+                Some(event) = self.events.recv() => {
+                    if let Err(e) = self.handle_event(event).await {
+                        error!("Error handling event: {}", e);
                     }
                 }
-                Some(Ok(Message::Close(_))) => {
-                    warn!("WebSocket connection closed");
-                    break;
+                Some(message) = self.outbox.recv() => {
+                    if let Err(e) = self.send_message(&message).await {
+                        error!("Error sending message: {}", e);
+                    }
                 }
-                Some(Err(e)) => {
-                    error!("WebSocket error: {}", e);
-                    break;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_event(&mut self, event: EventSubEvent) -> Result<()> {
+        match event {
+            EventSubEvent::Redemption(redemption) => {
+                info!(
+                    "{} redeemed {} ({})",
+                    redemption.user_login, redemption.reward.title, redemption.id
+                );
+                if redemption.reward.id == self.config.spotify.reward_id {
+                    let status = self
+                        .handle_spotify_reward(&redemption.user_login, &redemption.user_input)
+                        .await?;
+                    self.helix
+                        .update_redemption_status(&redemption.reward.id, &redemption.id, status)
+                        .await?;
                 }
-                None => {
-                    warn!("WebSocket stream ended");
-                    break;
+            }
+            EventSubEvent::Raid(raid) => {
+                info!(
+                    "Raided by {} with {} viewers",
+                    raid.from_broadcaster_user_login, raid.viewers
+                );
+                if let Some(response) = self
+                    .commands
+                    .handle_raid(&raid.from_broadcaster_user_login)
+                    .await
+                {
+                    self.send_message(&response).await?;
                 }
-                _ => {}
+            }
+            EventSubEvent::StreamOnline => {
+                info!("Stream went online");
+                self.commands.set_live(true);
+            }
+            EventSubEvent::StreamOffline => {
+                info!("Stream went offline");
+                self.commands.set_live(false);
             }
         }
         Ok(())
@@ -109,38 +200,77 @@ impl TwitchBot {
             return Ok(());
         }
 
+        if let Some(membership) = Membership::parse(raw_message, &self.config.twitch.channel) {
+            self.commands.record_membership(&membership);
+            return Ok(());
+        }
+
         if let Some(message) = TwitchMessage::parse(raw_message, &self.config.twitch.channel) {
-            if message.custom_reward_id.as_ref() == Some(&self.config.spotify.reward_id) {
-                self.handle_spotify_reward(&message).await?;
+            self.commands.record_chat_message(&message);
+
+            match self.commands.filter_message(&message).await {
+                Verdict::Allow => {}
+                Verdict::Warn(warning) => self.send_message(&warning).await?,
+                Verdict::Block(response) => {
+                    if let Some(response) = response {
+                        self.send_message(&response).await?;
+                    }
+                    return Ok(());
+                }
             }
 
-            if message.message.starts_with('!') {
-                self.handle_command(&message).await?;
+            for response in self.commands.dispatch_chat(&message).await {
+                self.send_message(&response).await?;
+            }
+
+            if let Some(greeting) = self.commands.greet(&message) {
+                self.send_message(&greeting).await?;
+            }
+
+            if !self.config.eventsub.enabled
+                && message.custom_reward_id.as_ref() == Some(&self.config.spotify.reward_id)
+            {
+                self.handle_spotify_reward(&message.username, &message.message)
+                    .await?;
+            }
+
+            if let Some(invocation) = self.commands.invocation(&message) {
+                self.handle_command(&message, &invocation).await?;
             }
         }
 
         Ok(())
     }
 
-    async fn handle_command(&mut self, message: &TwitchMessage) -> Result<()> {
-        let command_text = message.message.trim_start_matches('!');
-        let command_name = command_text.split_whitespace().next().unwrap_or("");
-
-        if let Some(response) = self.commands.execute(command_name, message).await {
+    async fn handle_command(
+        &mut self,
+        message: &TwitchMessage,
+        invocation: &Invocation<'_>,
+    ) -> Result<()> {
+        if let Some(response) = self.commands.execute(invocation, message).await {
             self.send_message(&response).await?;
         }
 
         Ok(())
     }
 
-    async fn handle_spotify_reward(&mut self, message: &TwitchMessage) -> Result<()> {
-        if message.message.contains("open.spotify.com/track/") {
-            if let Some(response) = self.commands.handle_spotify_reward(&message.message).await {
-                self.send_message(&response).await?;
-            }
-        } else {
+    async fn handle_spotify_reward(&mut self, user: &str, input: &str) -> Result<RedemptionStatus> {
+        if !input.contains("open.spotify.com/track/") {
             self.send_message("😭😂✌️").await?;
+            return Ok(RedemptionStatus::Canceled);
+        }
+
+        match self.commands.handle_spotify_reward(user, input).await {
+            Ok(track_info) => {
+                self.send_message(&format!("{} has been added to the queue :3", track_info))
+                    .await?;
+                Ok(RedemptionStatus::Fulfilled)
+            }
+            Err(e) => {
+                error!("Failed to add track: {}", e);
+                self.send_message("😭😂✌️").await?;
+                Ok(RedemptionStatus::Canceled)
+            }
         }
-        Ok(())
     }
 }
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{Command, custom::CommandTable};
+use crate::{bot::TwitchMessage, config::Permission};
+
+pub struct AliasCommand {
+    name: String,
+    target: String,
+    table: CommandTable,
+}
+
+impl AliasCommand {
+    pub fn new(name: String, target: String, table: CommandTable) -> Self {
+        Self {
+            name,
+            target,
+            table,
+        }
+    }
+
+    fn target(&self) -> Option<Arc<dyn Command>> {
+        self.table.read().unwrap().get(&self.target).cloned()
+    }
+}
+
+#[async_trait]
+impl Command for AliasCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn alias_of(&self) -> Option<&str> {
+        Some(&self.target)
+    }
+
+    fn permission(&self) -> Permission {
+        self.target()
+            .map(|command| command.permission())
+            .unwrap_or_default()
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let command = self.target()?;
+        command.execute(message).await
+    }
+}
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::error;
+
+use super::{Command, format::humanize_since};
+use crate::{bot::TwitchMessage, error::Result, helix::HelixClient};
+
+pub async fn stream_uptime(helix: &HelixClient) -> Result<Option<String>> {
+    let broadcaster_id = helix.broadcaster_id().await?;
+    let stream = helix.get_stream(&broadcaster_id).await?;
+    Ok(stream.map(|stream| humanize_since(stream.started_at, Utc::now())))
+}
+
+pub async fn current_game(helix: &HelixClient) -> Result<Option<String>> {
+    let broadcaster_id = helix.broadcaster_id().await?;
+    let channel = helix.get_channel(&broadcaster_id).await?;
+    Ok(channel
+        .map(|channel| channel.game_name)
+        .filter(|game| !game.is_empty()))
+}
+
+pub struct UptimeCommand {
+    helix: HelixClient,
+}
+
+impl UptimeCommand {
+    pub fn new(helix: HelixClient) -> Self {
+        Self { helix }
+    }
+
+    async fn uptime(&self) -> Result<String> {
+        match stream_uptime(&self.helix).await? {
+            Some(uptime) => Ok(format!("live for {}", uptime)),
+            None => Ok("stream is offline".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for UptimeCommand {
+    fn name(&self) -> &str {
+        "uptime"
+    }
+
+    fn description(&self) -> &str {
+        "shows how long the stream has been live"
+    }
+
+    async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
+        match self.uptime().await {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Uptime error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct TitleCommand {
+    helix: HelixClient,
+}
+
+impl TitleCommand {
+    pub fn new(helix: HelixClient) -> Self {
+        Self { helix }
+    }
+
+    async fn title(&self) -> Result<String> {
+        let broadcaster_id = self.helix.broadcaster_id().await?;
+        let channel = self.helix.get_channel(&broadcaster_id).await?;
+        Ok(channel
+            .map(|channel| channel.title)
+            .unwrap_or_else(|| "no title set".to_string()))
+    }
+
+    async fn set_title(&self, title: &str) -> Result<String> {
+        self.helix.modify_channel(Some(title), None).await?;
+        Ok(format!("title updated to: {}", title))
+    }
+}
+
+#[async_trait]
+impl Command for TitleCommand {
+    fn name(&self) -> &str {
+        "title"
+    }
+
+    fn description(&self) -> &str {
+        "shows the stream title, mods can change it"
+    }
+
+    fn usage(&self) -> &str {
+        "[new title]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let title = message.arg_text();
+
+        let result = if title.is_empty() || !message.is_moderator {
+            self.title().await
+        } else {
+            self.set_title(title).await
+        };
+
+        match result {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Title error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct GameCommand {
+    helix: HelixClient,
+}
+
+impl GameCommand {
+    pub fn new(helix: HelixClient) -> Self {
+        Self { helix }
+    }
+
+    async fn game(&self) -> Result<String> {
+        Ok(current_game(&self.helix)
+            .await?
+            .unwrap_or_else(|| "no category set".to_string()))
+    }
+
+    async fn set_game(&self, name: &str) -> Result<String> {
+        let category = match self.helix.get_category(name).await? {
+            Some(category) => Some(category),
+            None => self.helix.search_categories(name).await?.into_iter().next(),
+        };
+
+        let Some(category) = category else {
+            return Ok(format!("couldn't find a category called {}", name));
+        };
+
+        self.helix.modify_channel(None, Some(&category.id)).await?;
+        Ok(format!("category updated to: {}", category.name))
+    }
+}
+
+#[async_trait]
+impl Command for GameCommand {
+    fn name(&self) -> &str {
+        "game"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["category"]
+    }
+
+    fn description(&self) -> &str {
+        "shows the category, mods can change it"
+    }
+
+    fn usage(&self) -> &str {
+        "[category]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let name = message.arg_text();
+
+        let result = if name.is_empty() || !message.is_moderator {
+            self.game().await
+        } else {
+            self.set_game(name).await
+        };
+
+        match result {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Game error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{Command, custom::CommandTable, prefix::normalize_name};
+use crate::{
+    bot::TwitchMessage,
+    counters::{CounterEdit, CounterStore},
+};
+
+pub struct CounterCommand {
+    name: String,
+    store: CounterStore,
+}
+
+impl CounterCommand {
+    pub fn new(name: String, store: CounterStore) -> Self {
+        Self { name, store }
+    }
+}
+
+#[async_trait]
+impl Command for CounterCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "shows this counter, mods can change it"
+    }
+
+    fn usage(&self) -> &str {
+        "[+|-|set <number>]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+        if args.is_empty() {
+            return Some(format!("{}: {}", self.name, self.store.get(&self.name)));
+        }
+
+        if !message.is_moderator {
+            return Some("😭😂✌️".to_string());
+        }
+
+        let value = match args.as_slice() {
+            ["+"] => self.store.adjust(&self.name, 1),
+            ["-"] => self.store.adjust(&self.name, -1),
+            ["+", amount] | ["-", amount] | ["set", amount] => {
+                let Ok(amount) = amount.parse::<i64>() else {
+                    return Some(format!("{} isn't a number", amount));
+                };
+                match args[0] {
+                    "+" => self.store.adjust(&self.name, amount),
+                    "-" => self.store.adjust(&self.name, -amount),
+                    _ => self.store.set(&self.name, amount),
+                }
+            }
+            _ => {
+                return Some(format!("usage: !{} [+|-|set <number>]", self.name));
+            }
+        };
+
+        Some(format!("{}: {}", self.name, value))
+    }
+}
+
+pub struct CounterAdminCommand {
+    store: CounterStore,
+    table: CommandTable,
+}
+
+impl CounterAdminCommand {
+    pub fn new(store: CounterStore, table: CommandTable) -> Self {
+        Self { store, table }
+    }
+}
+
+#[async_trait]
+impl Command for CounterAdminCommand {
+    fn name(&self) -> &str {
+        "counter"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["counters"]
+    }
+
+    fn description(&self) -> &str {
+        "lists counters, mods can add and remove them"
+    }
+
+    fn usage(&self) -> &str {
+        "list or add|remove <name>"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+
+        match args.as_slice() {
+            [] | ["list"] => {
+                let counters = self.store.counters();
+                if counters.is_empty() {
+                    return Some("no counters yet".to_string());
+                }
+
+                let list = counters
+                    .iter()
+                    .map(|name| format!("{} ({})", name, self.store.get(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("counters: {}", list))
+            }
+            [_, ..] if !message.is_moderator => Some("😭😂✌️".to_string()),
+            ["add" | "create", name] => {
+                let name = normalize_name(name.trim_start_matches('!'));
+                let mut table = self.table.write().unwrap();
+                if table.contains_key(&name) {
+                    return Some(format!("!{} already exists", name));
+                }
+
+                match self.store.create(&name) {
+                    CounterEdit::Created => {
+                        let cmd = Arc::new(CounterCommand::new(name.clone(), self.store.clone()));
+                        table.insert(name.clone(), cmd);
+                        Some(format!("added counter !{}", name))
+                    }
+                    _ => Some(format!("!{} already exists", name)),
+                }
+            }
+            ["remove" | "delete", name] => {
+                let name = name.trim_start_matches('!').to_lowercase();
+                match self.store.remove(&name) {
+                    CounterEdit::Removed => {
+                        self.table.write().unwrap().remove(&name);
+                        Some(format!("removed counter !{}", name))
+                    }
+                    CounterEdit::Protected => Some(format!("!{} is set in config.toml", name)),
+                    _ => Some(format!("no counter called {}", name)),
+                }
+            }
+            _ => Some("usage: !counter list or !counter add|remove <name>".to_string()),
+        }
+    }
+}
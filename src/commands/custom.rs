@@ -0,0 +1,331 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use async_trait::async_trait;
+use tracing::{error, info};
+
+use super::{
+    Command,
+    prefix::normalize_name,
+    simple::{SimpleCommand, TemplateContext},
+};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, Permission, SimpleCommandConfig},
+    error::Result,
+    storage::SharedStorage,
+};
+
+pub type CommandTable = Arc<RwLock<HashMap<String, Arc<dyn Command>>>>;
+
+pub enum CustomEdit {
+    Added,
+    Edited,
+    Deleted,
+    Exists,
+    Missing,
+    Protected,
+    BuiltIn,
+}
+
+#[derive(Clone)]
+pub struct CustomCommands {
+    table: CommandTable,
+    storage: SharedStorage,
+    config_options: Arc<HashMap<String, SimpleCommandConfig>>,
+    context: TemplateContext,
+    responses: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl CustomCommands {
+    pub fn load(
+        config: &Config,
+        table: CommandTable,
+        storage: SharedStorage,
+        context: TemplateContext,
+    ) -> Self {
+        let config_options: HashMap<String, SimpleCommandConfig> = config
+            .commands
+            .simple
+            .iter()
+            .map(|(name, options)| (normalize_name(name), options.clone()))
+            .collect();
+        let mut responses: HashMap<String, String> = config_options
+            .iter()
+            .map(|(name, options)| (name.clone(), options.response.clone()))
+            .collect();
+
+        let stored = Self::stored(&storage, &mut responses).unwrap_or_else(|e| {
+            error!("Failed to load custom commands: {}", e);
+            0
+        });
+
+        info!(
+            "Loaded {} simple commands ({} from storage)",
+            responses.len(),
+            stored
+        );
+
+        Self {
+            table,
+            storage,
+            config_options: Arc::new(config_options),
+            context,
+            responses: Arc::new(Mutex::new(responses)),
+        }
+    }
+
+    fn stored(storage: &SharedStorage, responses: &mut HashMap<String, String>) -> Result<usize> {
+        for name in storage.deleted_commands()? {
+            responses.remove(&name);
+        }
+
+        let commands = storage.custom_commands()?;
+        let count = commands.len();
+        responses.extend(commands);
+        Ok(count)
+    }
+
+    pub fn simple_commands(&self) -> Vec<Arc<dyn Command>> {
+        let responses = self.responses.lock().unwrap();
+        responses
+            .iter()
+            .map(|(name, response)| self.build(name, response))
+            .collect()
+    }
+
+    fn build(&self, name: &str, response: &str) -> Arc<dyn Command> {
+        let command =
+            SimpleCommand::new(name.to_string(), response.to_string(), self.context.clone());
+        match self.config_options.get(name) {
+            Some(options) => Arc::new(command.with_limits(options.cooldown, options.permission)),
+            None => Arc::new(command),
+        }
+    }
+
+    pub fn response(&self, name: &str) -> Option<String> {
+        self.responses
+            .lock()
+            .unwrap()
+            .get(&normalize_name(name))
+            .cloned()
+    }
+
+    pub async fn render(&self, name: &str) -> Option<String> {
+        let response = self.response(name)?;
+        let command = SimpleCommand::new(normalize_name(name), response, self.context.clone());
+        Some(
+            command
+                .render(&self.context.config.twitch.channel, &[])
+                .await,
+        )
+    }
+
+    pub fn add(&self, name: &str, response: &str) -> CustomEdit {
+        let mut responses = self.responses.lock().unwrap();
+        if self.table.read().unwrap().contains_key(name) {
+            return CustomEdit::Exists;
+        }
+
+        if let Err(e) = self.storage.set_command_deleted(name, false) {
+            error!("Failed to save custom command {}: {}", name, e);
+        }
+        self.set(&mut responses, name, response);
+        CustomEdit::Added
+    }
+
+    pub fn edit(&self, name: &str, response: &str) -> CustomEdit {
+        let mut responses = self.responses.lock().unwrap();
+        if !responses.contains_key(name) {
+            return self.missing_or_builtin(name);
+        }
+
+        self.set(&mut responses, name, response);
+        CustomEdit::Edited
+    }
+
+    pub fn delete(&self, name: &str, force: bool) -> CustomEdit {
+        let mut responses = self.responses.lock().unwrap();
+        if !responses.contains_key(name) {
+            return self.missing_or_builtin(name);
+        }
+
+        let from_config = self.config_options.contains_key(name);
+        if from_config && !force {
+            return CustomEdit::Protected;
+        }
+
+        responses.remove(name);
+        let result = self.storage.delete_custom_command(name).and_then(|_| {
+            if from_config {
+                self.storage.set_command_deleted(name, true)
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(e) = result {
+            error!("Failed to delete custom command {}: {}", name, e);
+        }
+        self.table.write().unwrap().remove(name);
+        CustomEdit::Deleted
+    }
+
+    fn set(&self, responses: &mut HashMap<String, String>, name: &str, response: &str) {
+        responses.insert(name.to_string(), response.to_string());
+        if let Err(e) = self.storage.set_custom_command(name, response) {
+            error!("Failed to save custom command {}: {}", name, e);
+        }
+
+        let cmd = self.build(name, response);
+        self.table.write().unwrap().insert(name.to_string(), cmd);
+    }
+
+    fn missing_or_builtin(&self, name: &str) -> CustomEdit {
+        if self.table.read().unwrap().contains_key(name) {
+            CustomEdit::BuiltIn
+        } else {
+            CustomEdit::Missing
+        }
+    }
+}
+
+fn parse_definition(message: &TwitchMessage) -> Option<(String, &str)> {
+    let (name, response) = message.arg_text().split_once(char::is_whitespace)?;
+    let name = normalize_name(name.trim_start_matches('!'));
+    let response = response.trim();
+    (!name.is_empty() && !response.is_empty()).then_some((name, response))
+}
+
+pub struct AddComCommand {
+    custom: CustomCommands,
+}
+
+impl AddComCommand {
+    pub fn new(custom: CustomCommands) -> Self {
+        Self { custom }
+    }
+}
+
+#[async_trait]
+impl Command for AddComCommand {
+    fn name(&self) -> &str {
+        "addcom"
+    }
+
+    fn description(&self) -> &str {
+        "adds a custom command"
+    }
+
+    fn usage(&self) -> &str {
+        "<name> <response>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let Some((name, response)) = parse_definition(message) else {
+            return Some("usage: !addcom <name> <response>".to_string());
+        };
+
+        match self.custom.add(&name, response) {
+            CustomEdit::Added => Some(format!("added !{}", name)),
+            _ => Some(format!("!{} already exists", name)),
+        }
+    }
+}
+
+pub struct EditComCommand {
+    custom: CustomCommands,
+}
+
+impl EditComCommand {
+    pub fn new(custom: CustomCommands) -> Self {
+        Self { custom }
+    }
+}
+
+#[async_trait]
+impl Command for EditComCommand {
+    fn name(&self) -> &str {
+        "editcom"
+    }
+
+    fn description(&self) -> &str {
+        "changes a custom command"
+    }
+
+    fn usage(&self) -> &str {
+        "<name> <response>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let Some((name, response)) = parse_definition(message) else {
+            return Some("usage: !editcom <name> <response>".to_string());
+        };
+
+        match self.custom.edit(&name, response) {
+            CustomEdit::Edited => Some(format!("updated !{}", name)),
+            CustomEdit::BuiltIn => Some(format!("!{} is built in and can't be edited", name)),
+            _ => Some(format!("!{} doesn't exist", name)),
+        }
+    }
+}
+
+pub struct DelComCommand {
+    custom: CustomCommands,
+}
+
+impl DelComCommand {
+    pub fn new(custom: CustomCommands) -> Self {
+        Self { custom }
+    }
+}
+
+#[async_trait]
+impl Command for DelComCommand {
+    fn name(&self) -> &str {
+        "delcom"
+    }
+
+    fn description(&self) -> &str {
+        "deletes a custom command"
+    }
+
+    fn usage(&self) -> &str {
+        "<name> [--force]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+        let force = args.iter().any(|arg| matches!(*arg, "-f" | "--force"));
+        let Some(name) = args
+            .iter()
+            .find(|arg| !arg.starts_with('-'))
+            .map(|name| normalize_name(name.trim_start_matches('!')))
+        else {
+            return Some("usage: !delcom <name> [--force]".to_string());
+        };
+
+        match self.custom.delete(&name, force) {
+            CustomEdit::Deleted => Some(format!("deleted !{}", name)),
+            CustomEdit::Protected => Some(format!(
+                "!{} is set in config.toml, use !delcom {} --force",
+                name, name
+            )),
+            CustomEdit::BuiltIn => Some(format!("!{} is built in and can't be deleted", name)),
+            _ => Some(format!("!{} doesn't exist", name)),
+        }
+    }
+}
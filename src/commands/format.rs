@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Months, Timelike, Utc};
+
+pub fn humanize_since(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    if end <= start {
+        return "less than a minute".to_string();
+    }
+
+    let mut months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    if (end.day(), end.num_seconds_from_midnight())
+        < (start.day(), start.num_seconds_from_midnight())
+    {
+        months -= 1;
+    }
+    let months = months.max(0) as u32;
+
+    let anchor = start
+        .checked_add_months(Months::new(months))
+        .unwrap_or(start);
+    let remainder = end - anchor;
+
+    let units = [
+        (months as i64 / 12, "year"),
+        (months as i64 % 12, "month"),
+        (remainder.num_days(), "day"),
+        (remainder.num_hours() % 24, "hour"),
+        (remainder.num_minutes() % 60, "minute"),
+    ];
+
+    let parts: Vec<String> = units
+        .iter()
+        .skip_while(|(count, _)| *count == 0)
+        .take(2)
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| plural(*count, unit))
+        .collect();
+
+    if parts.is_empty() {
+        "less than a minute".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub fn humanize_duration(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    let units = [
+        (minutes / (60 * 24), "day"),
+        (minutes / 60 % 24, "hour"),
+        (minutes % 60, "minute"),
+    ];
+
+    let parts: Vec<String> = units
+        .iter()
+        .skip_while(|(count, _)| *count == 0)
+        .take(2)
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| plural(*count as i64, unit))
+        .collect();
+
+    if parts.is_empty() {
+        "less than a minute".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => text.split_at(index),
+        None => (text.as_str(), "m"),
+    };
+
+    let number: u64 = number.parse().ok()?;
+    let seconds = match unit {
+        "s" | "sec" | "secs" => number,
+        "m" | "min" | "mins" => number * 60,
+        "h" | "hr" | "hrs" => number * 60 * 60,
+        _ => return None,
+    };
+
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn counts_months_across_short_months() {
+        assert_eq!(
+            humanize_since(at(2025, 1, 31), at(2025, 3, 1)),
+            "1 month, 1 day"
+        );
+        assert_eq!(humanize_since(at(2025, 1, 31), at(2025, 3, 31)), "2 months");
+    }
+
+    #[test]
+    fn handles_leap_days() {
+        assert_eq!(humanize_since(at(2024, 2, 28), at(2024, 3, 1)), "2 days");
+        assert_eq!(humanize_since(at(2024, 2, 29), at(2025, 3, 1)), "1 year");
+        assert_eq!(humanize_since(at(2023, 3, 1), at(2024, 3, 1)), "1 year");
+    }
+
+    #[test]
+    fn short_spans_are_less_than_a_minute() {
+        let start = at(2025, 1, 1);
+        assert_eq!(
+            humanize_since(start, start + chrono::Duration::seconds(59)),
+            "less than a minute"
+        );
+        assert_eq!(humanize_since(start, start), "less than a minute");
+        assert_eq!(humanize_duration(0), "less than a minute");
+        assert_eq!(humanize_duration(59), "less than a minute");
+        assert_eq!(humanize_duration(60), "1 minute");
+    }
+
+    #[test]
+    fn durations_show_the_two_largest_units() {
+        assert_eq!(humanize_duration(90 * 60), "1 hour, 30 minutes");
+        assert_eq!(humanize_duration(2 * 86400 + 5 * 60), "2 days");
+        assert_eq!(humanize_duration(86400 + 3600 + 60), "1 day, 1 hour");
+    }
+}
@@ -0,0 +1,176 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tracing::error;
+
+use super::{Game, GameEngine, Step, already_running};
+use crate::{
+    bot::TwitchMessage,
+    commands::{
+        Command,
+        prefix::{command_name, parse_invocation},
+    },
+    config::Config,
+    loyalty::Loyalty,
+};
+
+struct Duel {
+    config: Arc<Config>,
+    challenger: String,
+    target: String,
+    wager: i64,
+    loyalty: Loyalty,
+}
+
+impl Duel {
+    fn refund(&self) {
+        if let Err(e) = self.loyalty.give(&self.challenger, self.wager) {
+            error!("Failed to refund duel wager to {}: {}", self.challenger, e);
+        }
+    }
+
+    fn fight(&self) -> String {
+        match self.loyalty.spend(&self.target, self.wager) {
+            Ok(true) => {}
+            Ok(false) => {
+                self.refund();
+                return format!("@{} doesn't have {} points", self.target, self.wager);
+            }
+            Err(e) => {
+                error!("Failed to take duel wager from {}: {}", self.target, e);
+                self.refund();
+                return "😭😂✌️".to_string();
+            }
+        }
+
+        let (winner, loser) = if rand::random::<bool>() {
+            (&self.challenger, &self.target)
+        } else {
+            (&self.target, &self.challenger)
+        };
+        if let Err(e) = self.loyalty.give(winner, self.wager * 2) {
+            error!("Failed to pay out duel to {}: {}", winner, e);
+        }
+        format!(
+            "⚔️ @{} beat @{} and won {} points",
+            winner, loser, self.wager
+        )
+    }
+}
+
+impl Game for Duel {
+    fn name(&self) -> &'static str {
+        "duel"
+    }
+
+    fn on_message(&mut self, message: &TwitchMessage) -> Step {
+        if !message.username.eq_ignore_ascii_case(&self.target) {
+            return Step::Ignore;
+        }
+
+        let Some(invocation) = parse_invocation(
+            &message.message,
+            &self.config.commands,
+            &self.config.twitch.username,
+        ) else {
+            return Step::Ignore;
+        };
+
+        match command_name(invocation.text).as_str() {
+            "accept" => Step::Finish(self.fight()),
+            "decline" => {
+                self.refund();
+                Step::Finish(format!("@{} chickened out", self.target))
+            }
+            _ => Step::Ignore,
+        }
+    }
+
+    fn on_timeout(&mut self) -> String {
+        self.refund();
+        format!("@{} didn't accept the duel", self.target)
+    }
+}
+
+pub struct DuelCommand {
+    config: Arc<Config>,
+    engine: GameEngine,
+    loyalty: Loyalty,
+}
+
+impl DuelCommand {
+    pub fn new(config: Arc<Config>, engine: GameEngine, loyalty: Loyalty) -> Self {
+        Self {
+            config,
+            engine,
+            loyalty,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for DuelCommand {
+    fn name(&self) -> &str {
+        "duel"
+    }
+
+    fn description(&self) -> &str {
+        "bets points against someone"
+    }
+
+    fn usage(&self) -> &str {
+        "@user <points>"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+        let (Some(target), Some(wager)) = (args.first(), args.get(1)) else {
+            return Some("usage: !duel @user <points>".to_string());
+        };
+
+        let challenger = message.username.to_lowercase();
+        let target = target.trim_start_matches('@').to_lowercase();
+        if target == challenger {
+            return Some("you can't duel yourself".to_string());
+        }
+
+        let min_wager = self.config.games.min_wager;
+        let wager = match wager.parse::<i64>() {
+            Ok(wager) if wager >= min_wager.max(1) => wager,
+            _ => return Some(format!("the wager has to be at least {} points", min_wager)),
+        };
+
+        match self.loyalty.spend(&challenger, wager) {
+            Ok(true) => {}
+            Ok(false) => return Some(format!("you don't have {} points", wager)),
+            Err(e) => {
+                error!("Failed to take duel wager from {}: {}", challenger, e);
+                return Some("😭😂✌️".to_string());
+            }
+        }
+
+        let game = Duel {
+            config: Arc::clone(&self.config),
+            challenger: challenger.clone(),
+            target: target.clone(),
+            wager,
+            loyalty: self.loyalty.clone(),
+        };
+        let timeout = Duration::from_secs(self.config.games.duel_timeout);
+        match self.engine.start(&message.channel, Box::new(game), timeout) {
+            Ok(()) => Some(format!(
+                "@{} challenged @{} to a duel for {} points! type !accept or !decline within {}s",
+                challenger,
+                target,
+                wager,
+                timeout.as_secs()
+            )),
+            Err(running) => {
+                if let Err(e) = self.loyalty.give(&challenger, wager) {
+                    error!("Failed to refund duel wager to {}: {}", challenger, e);
+                }
+                Some(already_running(running))
+            }
+        }
+    }
+}
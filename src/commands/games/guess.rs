@@ -0,0 +1,132 @@
+use std::{cmp::Ordering, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::Rng;
+
+use super::{Game, GameEngine, Step, already_running, reward};
+use crate::{
+    bot::TwitchMessage,
+    commands::Command,
+    config::{Config, Permission},
+    loyalty::Loyalty,
+};
+
+pub fn judge(secret: u32, max: u32, text: &str) -> Option<Ordering> {
+    let guess: u32 = text.trim().parse().ok()?;
+    (1..=max).contains(&guess).then(|| secret.cmp(&guess))
+}
+
+struct Guess {
+    secret: u32,
+    max: u32,
+    loyalty: Loyalty,
+    reward: i64,
+}
+
+impl Game for Guess {
+    fn name(&self) -> &'static str {
+        "guess"
+    }
+
+    fn on_message(&mut self, message: &TwitchMessage) -> Step {
+        let login = message.username.to_lowercase();
+        match judge(self.secret, self.max, &message.message) {
+            None => Step::Ignore,
+            Some(Ordering::Greater) => Step::Reply(format!("@{} higher", login)),
+            Some(Ordering::Less) => Step::Reply(format!("@{} lower", login)),
+            Some(Ordering::Equal) => Step::Finish(format!(
+                "@{} guessed it, the number was {}!{}",
+                login,
+                self.secret,
+                reward(&self.loyalty, &login, self.reward)
+            )),
+        }
+    }
+
+    fn on_timeout(&mut self) -> String {
+        format!("nobody guessed it, the number was {}", self.secret)
+    }
+}
+
+pub struct GuessCommand {
+    config: Arc<Config>,
+    engine: GameEngine,
+    loyalty: Loyalty,
+}
+
+impl GuessCommand {
+    pub fn new(config: Arc<Config>, engine: GameEngine, loyalty: Loyalty) -> Self {
+        Self {
+            config,
+            engine,
+            loyalty,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for GuessCommand {
+    fn name(&self) -> &str {
+        "guess"
+    }
+
+    fn description(&self) -> &str {
+        "starts a number guessing game"
+    }
+
+    fn usage(&self) -> &str {
+        "[max|stop]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let max = match message.arg_text() {
+            "" => self.config.games.guess_max,
+            arg if arg.eq_ignore_ascii_case("stop") => {
+                return Some(
+                    self.engine
+                        .stop(&message.channel)
+                        .unwrap_or_else(|| "there's no game running".to_string()),
+                );
+            }
+            arg => match arg.parse::<u32>() {
+                Ok(max) if max > 1 => max,
+                _ => return Some("usage: !guess [max]".to_string()),
+            },
+        };
+
+        let game = Guess {
+            secret: rand::rng().random_range(1..=max),
+            max,
+            loyalty: self.loyalty.clone(),
+            reward: self.config.games.reward,
+        };
+        let timeout = Duration::from_secs(self.config.games.timeout);
+        match self.engine.start(&message.channel, Box::new(game), timeout) {
+            Ok(()) => Some(format!("guess the number between 1 and {} in chat!", max)),
+            Err(running) => Some(already_running(running)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn judges_guesses_in_range() {
+        assert_eq!(judge(42, 100, "50"), Some(Ordering::Less));
+        assert_eq!(judge(42, 100, " 10 "), Some(Ordering::Greater));
+        assert_eq!(judge(42, 100, "42"), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn ignores_chat_and_out_of_range_numbers() {
+        assert_eq!(judge(42, 100, "gg"), None);
+        assert_eq!(judge(42, 100, "0"), None);
+        assert_eq!(judge(42, 100, "101"), None);
+    }
+}
@@ -0,0 +1,151 @@
+mod duel;
+mod guess;
+mod race;
+mod trivia;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tracing::error;
+
+use super::ChatListener;
+use crate::{bot::TwitchMessage, loyalty::Loyalty};
+
+pub use duel::DuelCommand;
+pub use guess::GuessCommand;
+pub use race::RaceCommand;
+pub use trivia::TriviaCommand;
+
+pub enum Step {
+    Ignore,
+    Reply(String),
+    Finish(String),
+}
+
+pub trait Game: Send {
+    fn name(&self) -> &'static str;
+    fn on_message(&mut self, message: &TwitchMessage) -> Step;
+    fn on_timeout(&mut self) -> String;
+}
+
+struct Running {
+    id: u64,
+    game: Box<dyn Game>,
+}
+
+#[derive(Default)]
+struct EngineState {
+    next_id: u64,
+    running: HashMap<String, Running>,
+}
+
+#[derive(Clone, Default)]
+pub struct GameEngine {
+    state: Arc<Mutex<EngineState>>,
+    outbox: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>,
+}
+
+impl GameEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn attach(&self, outbox: mpsc::UnboundedSender<String>) {
+        *self.outbox.lock().unwrap() = Some(outbox);
+    }
+
+    pub fn start(
+        &self,
+        channel: &str,
+        game: Box<dyn Game>,
+        timeout: Duration,
+    ) -> Result<(), &'static str> {
+        let channel = channel_key(channel);
+        let id = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(running) = state.running.get(&channel) {
+                return Err(running.game.name());
+            }
+            state.next_id += 1;
+            let id = state.next_id;
+            state.running.insert(channel.clone(), Running { id, game });
+            id
+        };
+
+        let engine = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            if let Some(message) = engine.finish(&channel, Some(id)) {
+                engine.send(message);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn stop(&self, channel: &str) -> Option<String> {
+        self.finish(&channel_key(channel), None)
+    }
+
+    fn finish(&self, channel: &str, id: Option<u64>) -> Option<String> {
+        let mut running = {
+            let mut state = self.state.lock().unwrap();
+            let current = state.running.get(channel)?;
+            if id.is_some_and(|id| id != current.id) {
+                return None;
+            }
+            state.running.remove(channel)?
+        };
+        Some(running.game.on_timeout())
+    }
+
+    fn send(&self, message: String) {
+        if let Some(outbox) = self.outbox.lock().unwrap().as_ref() {
+            let _ = outbox.send(message);
+        }
+    }
+}
+
+#[async_trait]
+impl ChatListener for GameEngine {
+    async fn on_message(&self, message: &TwitchMessage) -> Option<String> {
+        let channel = channel_key(&message.channel);
+        let mut state = self.state.lock().unwrap();
+        let running = state.running.get_mut(&channel)?;
+        match running.game.on_message(message) {
+            Step::Ignore => None,
+            Step::Reply(response) => Some(response),
+            Step::Finish(response) => {
+                state.running.remove(&channel);
+                Some(response)
+            }
+        }
+    }
+}
+
+fn channel_key(channel: &str) -> String {
+    channel.trim_start_matches('#').to_lowercase()
+}
+
+fn reward(loyalty: &Loyalty, login: &str, amount: i64) -> String {
+    if amount <= 0 {
+        return String::new();
+    }
+
+    match loyalty.give(login, amount) {
+        Ok(_) => format!(" (+{} points)", amount),
+        Err(e) => {
+            error!("Failed to reward {}: {}", login, e);
+            String::new()
+        }
+    }
+}
+
+fn already_running(game: &str) -> String {
+    format!("a game of {} is already running", game)
+}
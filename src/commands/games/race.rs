@@ -0,0 +1,104 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::seq::IndexedRandom;
+
+use super::{Game, GameEngine, Step, already_running, reward};
+use crate::{
+    bot::TwitchMessage,
+    commands::Command,
+    config::{Config, Permission},
+    loyalty::Loyalty,
+};
+
+struct Race {
+    word: String,
+    loyalty: Loyalty,
+    reward: i64,
+}
+
+impl Game for Race {
+    fn name(&self) -> &'static str {
+        "race"
+    }
+
+    fn on_message(&mut self, message: &TwitchMessage) -> Step {
+        if !message.message.trim().eq_ignore_ascii_case(&self.word) {
+            return Step::Ignore;
+        }
+
+        let login = message.username.to_lowercase();
+        Step::Finish(format!(
+            "@{} typed it first!{}",
+            login,
+            reward(&self.loyalty, &login, self.reward)
+        ))
+    }
+
+    fn on_timeout(&mut self) -> String {
+        format!("nobody typed {} in time", self.word)
+    }
+}
+
+pub struct RaceCommand {
+    config: Arc<Config>,
+    engine: GameEngine,
+    loyalty: Loyalty,
+}
+
+impl RaceCommand {
+    pub fn new(config: Arc<Config>, engine: GameEngine, loyalty: Loyalty) -> Self {
+        Self {
+            config,
+            engine,
+            loyalty,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for RaceCommand {
+    fn name(&self) -> &str {
+        "race"
+    }
+
+    fn description(&self) -> &str {
+        "starts a word race"
+    }
+
+    fn usage(&self) -> &str {
+        "[word|stop]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let word = match message.arg_text() {
+            "" => self.config.games.words.choose(&mut rand::rng()).cloned(),
+            word if word.eq_ignore_ascii_case("stop") => {
+                return Some(
+                    self.engine
+                        .stop(&message.channel)
+                        .unwrap_or_else(|| "there's no game running".to_string()),
+                );
+            }
+            word => Some(word.to_string()),
+        };
+        let Some(word) = word else {
+            return Some("usage: !race <word>, or set games.words".to_string());
+        };
+
+        let game = Race {
+            word: word.clone(),
+            loyalty: self.loyalty.clone(),
+            reward: self.config.games.reward,
+        };
+        let timeout = Duration::from_secs(self.config.games.timeout);
+        match self.engine.start(&message.channel, Box::new(game), timeout) {
+            Ok(()) => Some(format!("first to type {} wins!", word)),
+            Err(running) => Some(already_running(running)),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::{fs, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+use tracing::error;
+
+use super::{Game, GameEngine, Step, already_running, reward};
+use crate::{
+    bot::TwitchMessage,
+    commands::Command,
+    config::{Config, Permission},
+    error::Result,
+    loyalty::Loyalty,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Question {
+    pub question: String,
+    pub answers: Vec<String>,
+}
+
+pub fn parse_questions(text: &str) -> Result<Vec<Question>> {
+    let questions: Vec<Question> = serde_json::from_str(text)?;
+    Ok(questions
+        .into_iter()
+        .filter(|question| !question.answers.is_empty())
+        .collect())
+}
+
+pub fn normalize_answer(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+struct Trivia {
+    question: Question,
+    loyalty: Loyalty,
+    reward: i64,
+}
+
+impl Game for Trivia {
+    fn name(&self) -> &'static str {
+        "trivia"
+    }
+
+    fn on_message(&mut self, message: &TwitchMessage) -> Step {
+        let guess = normalize_answer(&message.message);
+        if !self
+            .question
+            .answers
+            .iter()
+            .any(|answer| normalize_answer(answer) == guess)
+        {
+            return Step::Ignore;
+        }
+
+        let login = message.username.to_lowercase();
+        Step::Finish(format!(
+            "@{} got it! the answer was {}{}",
+            login,
+            self.question.answers[0],
+            reward(&self.loyalty, &login, self.reward)
+        ))
+    }
+
+    fn on_timeout(&mut self) -> String {
+        format!("time's up! the answer was {}", self.question.answers[0])
+    }
+}
+
+pub struct TriviaCommand {
+    config: Arc<Config>,
+    engine: GameEngine,
+    loyalty: Loyalty,
+}
+
+impl TriviaCommand {
+    pub fn new(config: Arc<Config>, engine: GameEngine, loyalty: Loyalty) -> Self {
+        Self {
+            config,
+            engine,
+            loyalty,
+        }
+    }
+
+    fn load(&self) -> Result<Vec<Question>> {
+        parse_questions(&fs::read_to_string(&self.config.games.trivia_file)?)
+    }
+}
+
+#[async_trait]
+impl Command for TriviaCommand {
+    fn name(&self) -> &str {
+        "trivia"
+    }
+
+    fn description(&self) -> &str {
+        "starts a trivia question"
+    }
+
+    fn usage(&self) -> &str {
+        "[stop]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        if message.arg_text().eq_ignore_ascii_case("stop") {
+            return Some(
+                self.engine
+                    .stop(&message.channel)
+                    .unwrap_or_else(|| "there's no game running".to_string()),
+            );
+        }
+
+        let questions = match self.load() {
+            Ok(questions) => questions,
+            Err(e) => {
+                error!("Failed to load trivia questions: {}", e);
+                return Some("😭😂✌️".to_string());
+            }
+        };
+        let Some(question) = questions.choose(&mut rand::rng()).cloned() else {
+            return Some("there are no trivia questions yet".to_string());
+        };
+
+        let text = question.question.clone();
+        let game = Trivia {
+            question,
+            loyalty: self.loyalty.clone(),
+            reward: self.config.games.reward,
+        };
+        let timeout = Duration::from_secs(self.config.games.timeout);
+        match self.engine.start(&message.channel, Box::new(game), timeout) {
+            Ok(()) => Some(format!(
+                "trivia: {} (answer in chat, {}s)",
+                text,
+                timeout.as_secs()
+            )),
+            Err(running) => Some(already_running(running)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_punctuation() {
+        assert_eq!(normalize_answer("  The  Eiffel-Tower!"), "the eiffel tower");
+        assert_eq!(normalize_answer("42"), "42");
+        assert_eq!(normalize_answer("?!"), "");
+    }
+
+    #[test]
+    fn parses_question_bank() {
+        let questions = parse_questions(
+            r#"[
+                {"question": "capital of france?", "answers": ["Paris"]},
+                {"question": "no answers", "answers": []}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].answers, vec!["Paris"]);
+    }
+
+    #[test]
+    fn rejects_malformed_bank() {
+        assert!(parse_questions("question? answer").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use tracing::error;
+
+use super::{
+    ChatListener, Command,
+    format::{humanize_duration, parse_duration},
+};
+use crate::{
+    bot::TwitchMessage,
+    giveaway::{Draw, Giveaway},
+};
+
+pub struct GiveawayCommand {
+    giveaway: Giveaway,
+}
+
+impl GiveawayCommand {
+    pub fn new(giveaway: Giveaway) -> Self {
+        Self { giveaway }
+    }
+
+    fn start(&self, args: &[&str]) -> String {
+        let Some(keyword) = args.first() else {
+            return "usage: !giveaway start <keyword> [duration]".to_string();
+        };
+
+        let duration = match args.get(1) {
+            Some(text) => match parse_duration(text) {
+                Some(duration) => Some(duration),
+                None => return format!("{} isn't a duration, try 5m or 90s", text),
+            },
+            None => None,
+        };
+
+        self.giveaway.start(keyword, duration);
+        match duration {
+            Some(duration) => format!(
+                "giveaway started! type {} in chat to enter, entries close in {}",
+                keyword,
+                humanize_duration(duration.as_secs())
+            ),
+            None => format!("giveaway started! type {} in chat to enter", keyword),
+        }
+    }
+
+    async fn draw(&self, reroll: bool) -> String {
+        match self.giveaway.draw(reroll).await {
+            Ok(Draw::Winner { login, entries }) => format!(
+                "the winner is @{}! (drawn from {} {})",
+                login,
+                entries,
+                if entries == 1 { "entry" } else { "entries" }
+            ),
+            Ok(Draw::NoEligible) => "nobody eligible is left to draw".to_string(),
+            Ok(Draw::CheckFailed(unchecked)) => format!(
+                "couldn't check {} {} with twitch, try drawing again in a bit",
+                unchecked,
+                if unchecked == 1 { "entry" } else { "entries" }
+            ),
+            Ok(Draw::NotRunning) => "there's no giveaway running".to_string(),
+            Err(e) => {
+                error!("Giveaway draw error: {}", e);
+                "😭😂✌️".to_string()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Command for GiveawayCommand {
+    fn name(&self) -> &str {
+        "giveaway"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["raffle"]
+    }
+
+    fn description(&self) -> &str {
+        "shows the giveaway, mods can start and draw one"
+    }
+
+    fn usage(&self) -> &str {
+        "start <keyword> [duration], draw, reroll or cancel"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+
+        if let [] | ["status"] = args.as_slice() {
+            return Some(match self.giveaway.status() {
+                Some((keyword, entries, true)) => {
+                    format!(
+                        "type {} to enter the giveaway ({} entered)",
+                        keyword, entries
+                    )
+                }
+                Some((_, entries, false)) => {
+                    format!("entries are closed ({} entered)", entries)
+                }
+                None => "there's no giveaway running".to_string(),
+            });
+        }
+
+        if !message.is_moderator {
+            return Some("😭😂✌️".to_string());
+        }
+
+        Some(match args.as_slice() {
+            ["start", rest @ ..] => self.start(rest),
+            ["draw"] => self.draw(false).await,
+            ["reroll"] => self.draw(true).await,
+            ["cancel" | "stop"] => {
+                if self.giveaway.cancel() {
+                    "giveaway cancelled".to_string()
+                } else {
+                    "there's no giveaway running".to_string()
+                }
+            }
+            _ => "usage: !giveaway start <keyword> [duration], draw, reroll or cancel".to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl ChatListener for Giveaway {
+    async fn on_message(&self, message: &TwitchMessage) -> Option<String> {
+        self.enter(message);
+        None
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::{
+    bot::TwitchMessage,
+    config::Config,
+    template::{self, Template},
+};
+
+pub struct Greeter {
+    config: Arc<Config>,
+}
+
+impl Greeter {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    pub fn greet(&self, message: &TwitchMessage) -> Option<String> {
+        let greetings = &self.config.greetings;
+        if !greetings.enabled || message.is_broadcaster {
+            return None;
+        }
+
+        let template = if message.is_first_message {
+            &greetings.first_time
+        } else if message.is_returning_chatter {
+            &greetings.returning
+        } else {
+            return None;
+        };
+
+        if template.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::rng();
+        Some(Template::parse(template).render(|name, arg| match name {
+            "user" => Some(message.username.clone()),
+            "channel" => Some(self.config.twitch.channel.clone()),
+            other => template::builtin(other, arg, &mut rng),
+        }))
+    }
+}
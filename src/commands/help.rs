@@ -0,0 +1,317 @@
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::{error, info};
+
+use super::{
+    Command,
+    custom::CommandTable,
+    prefix::{display_prefix, normalize_name, parse_invocation},
+};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, Permission},
+    error::Result,
+};
+
+const CHAT_LIMIT: usize = 500;
+
+pub fn paginate(items: &[String], width: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+
+    for item in items {
+        if !page.is_empty() && page.chars().count() + 2 + item.chars().count() > width {
+            pages.push(std::mem::take(&mut page));
+        }
+        if !page.is_empty() {
+            page.push_str(", ");
+        }
+        page.push_str(item);
+    }
+    if !page.is_empty() {
+        pages.push(page);
+    }
+
+    pages
+}
+
+struct Entry {
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    usage: String,
+    permission: Permission,
+}
+
+fn canonical(command: &Arc<dyn Command>) -> &str {
+    command.alias_of().unwrap_or(command.name())
+}
+
+fn resolve<'a>(
+    commands: &'a HashMap<String, Arc<dyn Command>>,
+    key: &str,
+) -> Option<&'a Arc<dyn Command>> {
+    let command = commands.get(key)?;
+    if canonical(command) == key {
+        return Some(command);
+    }
+    commands.get(canonical(command)).or(Some(command))
+}
+
+fn entries(table: &CommandTable) -> Vec<Entry> {
+    let commands = table.read().unwrap();
+    let mut aliases: HashMap<&str, Vec<String>> = HashMap::new();
+    for (key, command) in commands.iter() {
+        if canonical(command) != key {
+            aliases
+                .entry(canonical(command))
+                .or_default()
+                .push(key.clone());
+        }
+    }
+
+    let mut entries: Vec<Entry> = commands
+        .iter()
+        .filter(|(key, command)| canonical(command) == key.as_str())
+        .map(|(key, command)| {
+            let mut aliases = aliases.remove(key.as_str()).unwrap_or_default();
+            aliases.sort();
+            Entry {
+                name: key.clone(),
+                aliases,
+                description: command.description().to_string(),
+                usage: command.usage().to_string(),
+                permission: command.permission(),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+fn synopsis(prefix: &str, entry: &Entry) -> String {
+    if entry.usage.is_empty() {
+        format!("{}{}", prefix, entry.name)
+    } else {
+        format!("{}{} {}", prefix, entry.name, entry.usage)
+    }
+}
+
+pub fn write_command_list(config: &Config, table: &CommandTable) {
+    let Some(path) = &config.commands.list_file else {
+        return;
+    };
+
+    let prefix = display_prefix(&config.commands);
+    let entries = entries(table);
+    let contents = if path.ends_with(".html") {
+        html_list(prefix, &entries)
+    } else {
+        markdown_list(prefix, &entries)
+    };
+
+    match write(path, &contents) {
+        Ok(()) => info!("Wrote {} commands to {}", entries.len(), path),
+        Err(e) => error!("Failed to write command list {}: {}", path, e),
+    }
+}
+
+fn write(path: &str, contents: &str) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn markdown_list(prefix: &str, entries: &[Entry]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = String::from(
+        "# Commands\n\n| command | description | aliases | who |\n| --- | --- | --- | --- |\n",
+    );
+    for entry in entries {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            cell(&synopsis(prefix, entry)),
+            cell(&entry.description),
+            cell(&entry.aliases.join(", ")),
+            entry.permission.label()
+        ));
+    }
+    out
+}
+
+fn html_list(prefix: &str, entries: &[Entry]) -> String {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut out = String::from(
+        "<!doctype html>\n<html>\n<head><meta charset=\"utf-8\"><title>Commands</title></head>\n<body>\n<table>\n<tr><th>command</th><th>description</th><th>aliases</th><th>who</th></tr>\n",
+    );
+    for entry in entries {
+        out.push_str(&format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&synopsis(prefix, entry)),
+            escape(&entry.description),
+            escape(&entry.aliases.join(", ")),
+            entry.permission.label()
+        ));
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+pub struct CommandsCommand {
+    config: Arc<Config>,
+    table: CommandTable,
+}
+
+impl CommandsCommand {
+    pub fn new(config: Arc<Config>, table: CommandTable) -> Self {
+        Self { config, table }
+    }
+}
+
+#[async_trait]
+impl Command for CommandsCommand {
+    fn name(&self) -> &str {
+        "commands"
+    }
+
+    fn description(&self) -> &str {
+        "lists the commands you can use"
+    }
+
+    fn usage(&self) -> &str {
+        "[page]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let prefix = display_prefix(&self.config.commands);
+        let names: Vec<String> = entries(&self.table)
+            .into_iter()
+            .filter(|entry| entry.permission <= message.permission())
+            .map(|entry| format!("{}{}", prefix, entry.name))
+            .collect();
+
+        let pages = paginate(&names, CHAT_LIMIT - 50);
+        if pages.len() <= 1 {
+            return Some(format!("commands: {}", pages.concat()));
+        }
+
+        let page = message
+            .arg_text()
+            .parse::<usize>()
+            .unwrap_or(1)
+            .clamp(1, pages.len());
+        let mut response = format!("commands ({}/{}): {}", page, pages.len(), pages[page - 1]);
+        if page < pages.len() {
+            response.push_str(&format!(" ({}commands {} for more)", prefix, page + 1));
+        }
+        Some(response)
+    }
+}
+
+pub struct HelpCommand {
+    config: Arc<Config>,
+    table: CommandTable,
+}
+
+impl HelpCommand {
+    pub fn new(config: Arc<Config>, table: CommandTable) -> Self {
+        Self { config, table }
+    }
+}
+
+#[async_trait]
+impl Command for HelpCommand {
+    fn name(&self) -> &str {
+        "help"
+    }
+
+    fn description(&self) -> &str {
+        "shows how to use a command"
+    }
+
+    fn usage(&self) -> &str {
+        "<command>"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let prefix = display_prefix(&self.config.commands);
+        let args = message.arg_text();
+        if args.is_empty() {
+            return Some(format!(
+                "usage: {}help <command>, or {}commands for the list",
+                prefix, prefix
+            ));
+        }
+
+        let name = parse_invocation(args, &self.config.commands, &self.config.twitch.username)
+            .map(|invocation| invocation.text)
+            .unwrap_or(args);
+        let name = normalize_name(name.split_whitespace().next().unwrap_or(""));
+        let key = {
+            let commands = self.table.read().unwrap();
+            resolve(&commands, &name).map(|command| command.name().to_string())
+        };
+        let Some(entry) = key.and_then(|key| {
+            entries(&self.table)
+                .into_iter()
+                .find(|entry| entry.name == key)
+        }) else {
+            return Some(format!("there's no {}{} command", prefix, name));
+        };
+
+        let mut response = synopsis(prefix, &entry);
+        if !entry.description.is_empty() {
+            response.push_str(&format!(" - {}", entry.description));
+        }
+        if !entry.aliases.is_empty() {
+            let aliases: Vec<String> = entry
+                .aliases
+                .iter()
+                .map(|alias| format!("{}{}", prefix, alias))
+                .collect();
+            response.push_str(&format!(" (also {})", aliases.join(", ")));
+        }
+        if entry.permission > Permission::Everyone {
+            response.push_str(&format!(" [{} only]", entry.permission.label()));
+        }
+        Some(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("!command{:02}", i)).collect()
+    }
+
+    #[test]
+    fn keeps_short_lists_on_one_page() {
+        assert_eq!(paginate(&names(2), 100), vec!["!command00, !command01"]);
+        assert!(paginate(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn splits_pages_within_width() {
+        let pages = paginate(&names(30), 60);
+
+        assert!(pages.len() > 1);
+        assert!(pages.iter().all(|page| page.chars().count() <= 60));
+        assert_eq!(pages.join(", "), names(30).join(", "));
+    }
+
+    #[test]
+    fn keeps_oversized_items_whole() {
+        let long = vec!["x".repeat(20), "y".repeat(5)];
+        assert_eq!(paginate(&long, 10), vec!["x".repeat(20), "y".repeat(5)]);
+    }
+}
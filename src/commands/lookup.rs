@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::error;
+
+use super::{Command, format::humanize_since};
+use crate::{bot::TwitchMessage, error::Result, helix::HelixClient};
+
+struct Target {
+    id: String,
+    login: String,
+}
+
+async fn resolve_target(helix: &HelixClient, message: &TwitchMessage) -> Result<Option<Target>> {
+    let login = message
+        .arg_text()
+        .split_whitespace()
+        .next()
+        .map(|login| login.trim_start_matches('@').to_lowercase());
+
+    match login {
+        Some(login) if login != message.username.to_lowercase() => {
+            Ok(helix.get_user(&login).await?.map(|user| Target {
+                id: user.id,
+                login: user.login,
+            }))
+        }
+        _ if !message.user_id.is_empty() => Ok(Some(Target {
+            id: message.user_id.clone(),
+            login: message.username.clone(),
+        })),
+        _ => Ok(helix.get_user(&message.username).await?.map(|user| Target {
+            id: user.id,
+            login: user.login,
+        })),
+    }
+}
+
+pub struct FollowageCommand {
+    helix: HelixClient,
+}
+
+impl FollowageCommand {
+    pub fn new(helix: HelixClient) -> Self {
+        Self { helix }
+    }
+
+    async fn followage(&self, message: &TwitchMessage) -> Result<String> {
+        let Some(target) = resolve_target(&self.helix, message).await? else {
+            return Ok("couldn't find that user".to_string());
+        };
+
+        if target.id == self.helix.broadcaster_id().await? {
+            return Ok("you can't follow yourself silly".to_string());
+        }
+
+        match self.helix.get_follower(&target.id).await? {
+            Some(follower) => Ok(format!(
+                "{} has been following for {}",
+                target.login,
+                humanize_since(follower.followed_at, Utc::now())
+            )),
+            None => Ok(format!("{} isn't following", target.login)),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for FollowageCommand {
+    fn name(&self) -> &str {
+        "followage"
+    }
+
+    fn description(&self) -> &str {
+        "shows how long someone has followed"
+    }
+
+    fn usage(&self) -> &str {
+        "[user]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        match self.followage(message).await {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Followage error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct AccountageCommand {
+    helix: HelixClient,
+}
+
+impl AccountageCommand {
+    pub fn new(helix: HelixClient) -> Self {
+        Self { helix }
+    }
+
+    async fn accountage(&self, message: &TwitchMessage) -> Result<String> {
+        let login = message
+            .arg_text()
+            .split_whitespace()
+            .next()
+            .map(|login| login.trim_start_matches('@').to_lowercase())
+            .unwrap_or_else(|| message.username.to_lowercase());
+
+        let Some(user) = self.helix.get_user(&login).await? else {
+            return Ok("couldn't find that user".to_string());
+        };
+
+        Ok(format!(
+            "{}'s account is {} old",
+            user.login,
+            humanize_since(user.created_at, Utc::now())
+        ))
+    }
+}
+
+#[async_trait]
+impl Command for AccountageCommand {
+    fn name(&self) -> &str {
+        "accountage"
+    }
+
+    fn description(&self) -> &str {
+        "shows how old an account is"
+    }
+
+    fn usage(&self) -> &str {
+        "[user]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        match self.accountage(message).await {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Accountage error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use tracing::error;
+
+use chrono::Utc;
+
+use super::{
+    Command,
+    format::{humanize_duration, humanize_since},
+};
+use crate::{
+    bot::TwitchMessage, config::Permission, error::Result, loyalty::Loyalty, storage::SharedStorage,
+};
+
+const TOP_LIMIT: usize = 5;
+
+fn target_login(message: &TwitchMessage) -> String {
+    message
+        .arg_text()
+        .split_whitespace()
+        .next()
+        .map(|login| login.trim_start_matches('@').to_lowercase())
+        .unwrap_or_else(|| message.username.to_lowercase())
+}
+
+fn respond(result: Result<String>, context: &str) -> Option<String> {
+    match result {
+        Ok(response) => Some(response),
+        Err(e) => {
+            error!("{} error: {}", context, e);
+            Some("😭😂✌️".to_string())
+        }
+    }
+}
+
+pub struct PointsCommand {
+    loyalty: Loyalty,
+}
+
+impl PointsCommand {
+    pub fn new(loyalty: Loyalty) -> Self {
+        Self { loyalty }
+    }
+}
+
+#[async_trait]
+impl Command for PointsCommand {
+    fn name(&self) -> &str {
+        "points"
+    }
+
+    fn description(&self) -> &str {
+        "shows someone's points"
+    }
+
+    fn usage(&self) -> &str {
+        "[user]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let login = target_login(message);
+        let result = self
+            .loyalty
+            .points(&login)
+            .map(|points| format!("{} has {} points", login, points));
+        respond(result, "Points")
+    }
+}
+
+pub struct WatchtimeCommand {
+    loyalty: Loyalty,
+}
+
+impl WatchtimeCommand {
+    pub fn new(loyalty: Loyalty) -> Self {
+        Self { loyalty }
+    }
+}
+
+#[async_trait]
+impl Command for WatchtimeCommand {
+    fn name(&self) -> &str {
+        "watchtime"
+    }
+
+    fn description(&self) -> &str {
+        "shows someone's watch time"
+    }
+
+    fn usage(&self) -> &str {
+        "[user]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let login = target_login(message);
+        let result = self
+            .loyalty
+            .watch_time(&login)
+            .map(|seconds| format!("{} has watched for {}", login, humanize_duration(seconds)));
+        respond(result, "Watchtime")
+    }
+}
+
+pub struct StatsCommand {
+    storage: SharedStorage,
+}
+
+impl StatsCommand {
+    pub fn new(storage: SharedStorage) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl Command for StatsCommand {
+    fn name(&self) -> &str {
+        "stats"
+    }
+
+    fn description(&self) -> &str {
+        "shows how much someone has chatted"
+    }
+
+    fn usage(&self) -> &str {
+        "[user]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let login = target_login(message);
+        let result = self.storage.user_stats(&login).map(|stats| match stats {
+            Some(stats) => format!(
+                "{} has sent {} messages since {} ago",
+                login,
+                stats.messages,
+                humanize_since(stats.first_seen, Utc::now())
+            ),
+            None => format!("{} hasn't chatted yet", login),
+        });
+        respond(result, "Stats")
+    }
+}
+
+pub struct TopCommand {
+    loyalty: Loyalty,
+}
+
+impl TopCommand {
+    pub fn new(loyalty: Loyalty) -> Self {
+        Self { loyalty }
+    }
+
+    fn top(&self, board: &str) -> Result<String> {
+        let entries: Vec<String> = match board {
+            "points" => self
+                .loyalty
+                .top_points(TOP_LIMIT)?
+                .into_iter()
+                .map(|(login, points)| format!("{} ({})", login, points))
+                .collect(),
+            "watchtime" => self
+                .loyalty
+                .top_watch_time(TOP_LIMIT)?
+                .into_iter()
+                .map(|(login, seconds)| format!("{} ({})", login, humanize_duration(seconds)))
+                .collect(),
+            _ => return Ok("usage: !top points or !top watchtime".to_string()),
+        };
+
+        if entries.is_empty() {
+            return Ok("nobody on the board yet".to_string());
+        }
+
+        let list = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| format!("{}. {}", index + 1, entry))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(format!("top {}: {}", board, list))
+    }
+}
+
+#[async_trait]
+impl Command for TopCommand {
+    fn name(&self) -> &str {
+        "top"
+    }
+
+    fn description(&self) -> &str {
+        "shows the leaderboard"
+    }
+
+    fn usage(&self) -> &str {
+        "[points|watchtime]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let board = message
+            .arg_text()
+            .split_whitespace()
+            .next()
+            .unwrap_or("points")
+            .to_lowercase();
+        respond(self.top(&board), "Top")
+    }
+}
+
+pub struct GivePointsCommand {
+    loyalty: Loyalty,
+}
+
+impl GivePointsCommand {
+    pub fn new(loyalty: Loyalty) -> Self {
+        Self { loyalty }
+    }
+}
+
+#[async_trait]
+impl Command for GivePointsCommand {
+    fn name(&self) -> &str {
+        "givepoints"
+    }
+
+    fn description(&self) -> &str {
+        "gives or takes points"
+    }
+
+    fn usage(&self) -> &str {
+        "<user> <amount>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+        let [login, amount] = args.as_slice() else {
+            return Some("usage: !givepoints <user> <amount>".to_string());
+        };
+        let Ok(amount) = amount.parse::<i64>() else {
+            return Some(format!("{} isn't a number", amount));
+        };
+
+        let login = login.trim_start_matches('@').to_lowercase();
+        let result = self
+            .loyalty
+            .give(&login, amount)
+            .map(|points| format!("{} now has {} points", login, points));
+        respond(result, "Givepoints")
+    }
+}
@@ -1,11 +1,49 @@
+mod alias;
+mod channel;
+mod counter;
+mod custom;
+mod format;
+mod games;
+mod giveaway;
+mod greetings;
+mod help;
+mod lookup;
+mod loyalty;
+mod moderation;
+mod poll;
+mod prefix;
+mod quote;
+mod shoutout;
 mod simple;
 mod spotify;
+mod timer;
+mod triggers;
 
 use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::mpsc;
+use tracing::{error, warn};
 
-use crate::{bot::TwitchMessage, config::Config};
+use crate::{
+    bot::{Membership, TwitchMessage},
+    config::{Config, Permission},
+    counters::CounterStore,
+    giveaway::Giveaway,
+    helix::HelixClient,
+    loyalty::Loyalty,
+    moderation::{Blocklist, ChatFilter, ModerationService, Verdict, audit::AuditLog},
+    polls::Polls,
+    quotes::QuoteBook,
+    storage::{SharedStorage, SongPlay},
+    timers::Timers,
+};
+
+pub use custom::CustomCommands;
+pub use prefix::Invocation;
+pub use simple::TemplateContext;
+pub use spotify::SpotifyService;
 
 #[async_trait]
 pub trait Command: Send + Sync {
@@ -13,40 +51,312 @@ pub trait Command: Send + Sync {
     fn aliases(&self) -> Vec<&str> {
         vec![]
     }
+    fn alias_of(&self) -> Option<&str> {
+        None
+    }
+    fn description(&self) -> &str {
+        ""
+    }
+    fn usage(&self) -> &str {
+        ""
+    }
+    fn permission(&self) -> Permission {
+        Permission::Everyone
+    }
     async fn execute(&self, message: &TwitchMessage) -> Option<String>;
 }
 
+#[async_trait]
+pub trait ChatListener: Send + Sync {
+    async fn on_message(&self, message: &TwitchMessage) -> Option<String>;
+}
+
 pub struct CommandRegistry {
-    commands: HashMap<String, Arc<dyn Command>>,
-    spotify_service: spotify::SpotifyService,
+    config: Arc<Config>,
+    commands: custom::CommandTable,
+    spotify_service: SpotifyService,
+    shoutout_service: shoutout::ShoutoutService,
+    chat_filter: ChatFilter,
+    greeter: greetings::Greeter,
+    timers: Timers,
+    loyalty: Loyalty,
+    polls: Polls,
+    games: games::GameEngine,
+    listeners: Vec<Arc<dyn ChatListener>>,
+    storage: SharedStorage,
 }
 
 impl CommandRegistry {
-    pub fn new(config: Arc<Config>) -> Self {
-        let mut commands = HashMap::new();
-        let spotify_service = spotify::SpotifyService::new(Arc::clone(&config));
+    pub fn new(config: Arc<Config>, helix: HelixClient, storage: SharedStorage) -> Self {
+        let spotify_service = SpotifyService::new(Arc::clone(&config));
+        let counters = CounterStore::new(&config.counters, Arc::clone(&storage));
+        let template_context = TemplateContext {
+            config: Arc::clone(&config),
+            helix: helix.clone(),
+            spotify: spotify_service.clone(),
+            counters: counters.clone(),
+        };
+        let table = custom::CommandTable::default();
+        let custom_commands = custom::CustomCommands::load(
+            &config,
+            Arc::clone(&table),
+            Arc::clone(&storage),
+            template_context.clone(),
+        );
+        let mut commands = table.write().unwrap();
+        let shoutout_service = shoutout::ShoutoutService::new(
+            Arc::clone(&config),
+            helix.clone(),
+            Arc::clone(&storage),
+        );
+        let moderation_service =
+            ModerationService::new(helix.clone(), AuditLog::new(&config.moderation.audit_log));
+        let blocklist = Blocklist::new(&config.moderation.blocklist);
+        let chat_filter = ChatFilter::new(
+            Arc::clone(&config),
+            moderation_service.clone(),
+            blocklist.clone(),
+        );
+
+        let loyalty = Loyalty::new(Arc::clone(&config), helix.clone(), Arc::clone(&storage));
+
+        Self::register_spotify_commands(
+            &mut commands,
+            &config,
+            &spotify_service,
+            &loyalty,
+            &storage,
+        );
+        Self::register_channel_commands(
+            &mut commands,
+            &config,
+            &helix,
+            &shoutout_service,
+            &storage,
+        );
+        Self::register_moderation_commands(
+            &mut commands,
+            &moderation_service,
+            &chat_filter,
+            &blocklist,
+            &config,
+        );
+        let timers = Timers::new(Arc::clone(&config), custom_commands.clone(), helix.clone());
+        Self::insert_commands(
+            &mut commands,
+            vec![Arc::new(timer::TimerCommand::new(timers.clone()))],
+        );
+        Self::register_counter_commands(&mut commands, &counters, &table);
+        Self::register_loyalty_commands(&mut commands, &loyalty, &storage);
+        let giveaway = Giveaway::new(Arc::clone(&config), helix.clone(), Arc::clone(&storage));
+        Self::insert_commands(
+            &mut commands,
+            vec![Arc::new(giveaway::GiveawayCommand::new(giveaway.clone()))],
+        );
+        let polls = Polls::new(Arc::clone(&config), helix.clone());
+        Self::insert_commands(
+            &mut commands,
+            vec![
+                Arc::new(poll::PollCommand::new(polls.clone())),
+                Arc::new(poll::VoteCommand::new(polls.clone())),
+            ],
+        );
+        let games = games::GameEngine::new();
+        Self::register_game_commands(&mut commands, &config, &games, &loyalty);
+        Self::insert_commands(
+            &mut commands,
+            vec![
+                Arc::new(help::CommandsCommand::new(
+                    Arc::clone(&config),
+                    Arc::clone(&table),
+                )),
+                Arc::new(help::HelpCommand::new(
+                    Arc::clone(&config),
+                    Arc::clone(&table),
+                )),
+            ],
+        );
+        Self::register_simple_commands(&mut commands, &custom_commands);
+        Self::register_aliases(&mut commands, &config, &table);
+        drop(commands);
+        help::write_command_list(&config, &table);
+
+        let listeners: Vec<Arc<dyn ChatListener>> = vec![
+            Arc::new(giveaway),
+            Arc::new(polls.clone()),
+            Arc::new(games.clone()),
+            Arc::new(triggers::Triggers::new(
+                Arc::clone(&config),
+                Arc::clone(&table),
+                template_context,
+            )),
+        ];
 
-        Self::register_spotify_commands(&mut commands, &spotify_service);
-        Self::register_simple_commands(&mut commands, &config);
+        let greeter = greetings::Greeter::new(Arc::clone(&config));
 
         Self {
-            commands,
+            config,
+            commands: table,
             spotify_service,
+            shoutout_service,
+            chat_filter,
+            greeter,
+            timers,
+            loyalty,
+            polls,
+            games,
+            listeners,
+            storage,
         }
     }
 
     fn register_spotify_commands(
         commands: &mut HashMap<String, Arc<dyn Command>>,
-        service: &spotify::SpotifyService,
+        config: &Arc<Config>,
+        service: &SpotifyService,
+        loyalty: &Loyalty,
+        storage: &SharedStorage,
     ) {
         let spotify_commands: Vec<Arc<dyn Command>> = vec![
             Arc::new(spotify::SpotifyCommand::new(service.clone())),
             Arc::new(spotify::PlayCommand::new(service.clone())),
             Arc::new(spotify::SkipCommand::new(service.clone())),
             Arc::new(spotify::PrevCommand::new(service.clone())),
+            Arc::new(spotify::SongsCommand::new(Arc::clone(storage))),
+            Arc::new(spotify::SongRequestCommand::new(
+                Arc::clone(config),
+                service.clone(),
+                loyalty.clone(),
+                Arc::clone(storage),
+            )),
         ];
 
-        for cmd in spotify_commands {
+        Self::insert_commands(commands, spotify_commands);
+    }
+
+    fn register_channel_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        config: &Arc<Config>,
+        helix: &HelixClient,
+        shoutout_service: &shoutout::ShoutoutService,
+        storage: &SharedStorage,
+    ) {
+        let channel_commands: Vec<Arc<dyn Command>> = vec![
+            Arc::new(channel::UptimeCommand::new(helix.clone())),
+            Arc::new(channel::TitleCommand::new(helix.clone())),
+            Arc::new(channel::GameCommand::new(helix.clone())),
+            Arc::new(shoutout::ShoutoutCommand::new(shoutout_service.clone())),
+            Arc::new(lookup::FollowageCommand::new(helix.clone())),
+            Arc::new(lookup::AccountageCommand::new(helix.clone())),
+            Arc::new(quote::QuoteCommand::new(
+                Arc::clone(config),
+                helix.clone(),
+                QuoteBook::new(&config.quotes, Arc::clone(storage)),
+            )),
+        ];
+
+        Self::insert_commands(commands, channel_commands);
+    }
+
+    fn register_moderation_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        service: &ModerationService,
+        filter: &ChatFilter,
+        blocklist: &Blocklist,
+        config: &Config,
+    ) {
+        let moderation_commands: Vec<Arc<dyn Command>> = vec![
+            Arc::new(moderation::TimeoutCommand::new(
+                service.clone(),
+                config.moderation.default_timeout,
+            )),
+            Arc::new(moderation::BanCommand::new(service.clone())),
+            Arc::new(moderation::UnbanCommand::new(service.clone())),
+            Arc::new(moderation::ClearCommand::new(service.clone())),
+            Arc::new(moderation::ModlogCommand::new(service.clone())),
+            Arc::new(moderation::PermitCommand::new(filter.clone())),
+            Arc::new(moderation::BlockCommand::new(blocklist.clone())),
+            Arc::new(moderation::ApproveCommand::new(
+                filter.clone(),
+                service.clone(),
+            )),
+            Arc::new(moderation::DenyCommand::new(filter.clone())),
+        ];
+
+        Self::insert_commands(commands, moderation_commands);
+    }
+
+    fn register_counter_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        counters: &CounterStore,
+        table: &custom::CommandTable,
+    ) {
+        let mut counter_commands: Vec<Arc<dyn Command>> = vec![Arc::new(
+            counter::CounterAdminCommand::new(counters.clone(), Arc::clone(table)),
+        )];
+        for name in counters.counters() {
+            counter_commands.push(Arc::new(counter::CounterCommand::new(
+                name,
+                counters.clone(),
+            )));
+        }
+
+        Self::insert_commands(commands, counter_commands);
+    }
+
+    fn register_loyalty_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        loyalty: &Loyalty,
+        storage: &SharedStorage,
+    ) {
+        let loyalty_commands: Vec<Arc<dyn Command>> = vec![
+            Arc::new(loyalty::PointsCommand::new(loyalty.clone())),
+            Arc::new(loyalty::WatchtimeCommand::new(loyalty.clone())),
+            Arc::new(loyalty::TopCommand::new(loyalty.clone())),
+            Arc::new(loyalty::StatsCommand::new(Arc::clone(storage))),
+            Arc::new(loyalty::GivePointsCommand::new(loyalty.clone())),
+        ];
+
+        Self::insert_commands(commands, loyalty_commands);
+    }
+
+    fn register_game_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        config: &Arc<Config>,
+        engine: &games::GameEngine,
+        loyalty: &Loyalty,
+    ) {
+        let game_commands: Vec<Arc<dyn Command>> = vec![
+            Arc::new(games::TriviaCommand::new(
+                Arc::clone(config),
+                engine.clone(),
+                loyalty.clone(),
+            )),
+            Arc::new(games::RaceCommand::new(
+                Arc::clone(config),
+                engine.clone(),
+                loyalty.clone(),
+            )),
+            Arc::new(games::GuessCommand::new(
+                Arc::clone(config),
+                engine.clone(),
+                loyalty.clone(),
+            )),
+            Arc::new(games::DuelCommand::new(
+                Arc::clone(config),
+                engine.clone(),
+                loyalty.clone(),
+            )),
+        ];
+
+        Self::insert_commands(commands, game_commands);
+    }
+
+    fn insert_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        new_commands: Vec<Arc<dyn Command>>,
+    ) {
+        for cmd in new_commands {
             commands.insert(cmd.name().to_string(), Arc::clone(&cmd));
             for alias in cmd.aliases() {
                 commands.insert(alias.to_string(), Arc::clone(&cmd));
@@ -54,22 +364,190 @@ impl CommandRegistry {
         }
     }
 
-    fn register_simple_commands(commands: &mut HashMap<String, Arc<dyn Command>>, config: &Config) {
-        for (name, response) in &config.commands.simple {
-            let cmd = Arc::new(simple::SimpleCommand::new(name.clone(), response.clone()));
-            commands.insert(name.clone(), cmd);
+    fn register_simple_commands(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        custom_commands: &custom::CustomCommands,
+    ) {
+        let custom_admin: Vec<Arc<dyn Command>> = vec![
+            Arc::new(custom::AddComCommand::new(custom_commands.clone())),
+            Arc::new(custom::EditComCommand::new(custom_commands.clone())),
+            Arc::new(custom::DelComCommand::new(custom_commands.clone())),
+        ];
+        Self::insert_commands(commands, custom_admin);
+
+        for cmd in custom_commands.simple_commands() {
+            if commands.contains_key(cmd.name()) {
+                warn!(
+                    "Simple command {} is already a command, skipping it",
+                    cmd.name()
+                );
+            } else {
+                commands.insert(cmd.name().to_string(), cmd);
+            }
+        }
+    }
+
+    fn register_aliases(
+        commands: &mut HashMap<String, Arc<dyn Command>>,
+        config: &Config,
+        table: &custom::CommandTable,
+    ) {
+        let simple_aliases = config.commands.simple.iter().flat_map(|(name, options)| {
+            options
+                .aliases
+                .iter()
+                .map(move |alias| (alias.as_str(), name.as_str()))
+        });
+        let aliases: Vec<(String, String)> = simple_aliases
+            .chain(
+                config
+                    .commands
+                    .aliases
+                    .iter()
+                    .map(|(alias, target)| (alias.as_str(), target.as_str())),
+            )
+            .map(|(alias, target)| {
+                (
+                    prefix::normalize_name(alias.trim_start_matches('!')),
+                    prefix::normalize_name(target.trim_start_matches('!')),
+                )
+            })
+            .collect();
+
+        for (alias, target) in &aliases {
+            if commands.contains_key(alias) {
+                warn!("Alias {} is already a command, skipping it", alias);
+            } else if aliases.iter().any(|(other, _)| other == target) {
+                warn!("Alias {} points at another alias {}", alias, target);
+            } else if let Some(command) = commands.get(target) {
+                let target = command.name().to_string();
+                commands.insert(
+                    alias.clone(),
+                    Arc::new(alias::AliasCommand::new(
+                        alias.clone(),
+                        target,
+                        Arc::clone(table),
+                    )),
+                );
+            } else {
+                warn!("Alias {} points at unknown command {}", alias, target);
+            }
+        }
+    }
+
+    pub fn invocation<'a>(&self, message: &'a TwitchMessage) -> Option<Invocation<'a>> {
+        prefix::parse_invocation(
+            &message.message,
+            &self.config.commands,
+            &self.config.twitch.username,
+        )
+    }
+
+    pub async fn execute(
+        &self,
+        invocation: &Invocation<'_>,
+        message: &TwitchMessage,
+    ) -> Option<String> {
+        let name = prefix::command_name(invocation.text);
+        let command = self.commands.read().unwrap().get(&name).cloned();
+        match command {
+            Some(command) => {
+                if message.permission() < command.permission() {
+                    return Some("😭😂✌️".to_string());
+                }
+                let message = TwitchMessage {
+                    message: invocation.text.to_string(),
+                    ..message.clone()
+                };
+                command.execute(&message).await
+            }
+            None => self.suggestion(&name, invocation),
+        }
+    }
+
+    fn suggestion(&self, name: &str, invocation: &Invocation<'_>) -> Option<String> {
+        if !self.config.commands.suggestions || invocation.mentioned {
+            return None;
+        }
+
+        let commands = self.commands.read().unwrap();
+        let suggestion = prefix::suggest(name, commands.keys().map(String::as_str))?;
+        Some(format!(
+            "did you mean {}{}?",
+            prefix::display_prefix(&self.config.commands),
+            suggestion
+        ))
+    }
+
+    pub async fn filter_message(&self, message: &TwitchMessage) -> Verdict {
+        self.chat_filter.check(message).await
+    }
+
+    pub fn start_timers(&self, outbox: mpsc::UnboundedSender<String>) {
+        self.polls.attach(outbox.clone());
+        self.games.attach(outbox.clone());
+        self.timers.spawn(outbox);
+        self.loyalty.spawn();
+    }
+
+    pub fn record_membership(&self, membership: &Membership) {
+        self.loyalty.record_membership(membership);
+    }
+
+    pub fn set_live(&self, live: bool) {
+        self.loyalty.set_live(live);
+    }
+
+    pub fn record_chat_message(&self, message: &TwitchMessage) {
+        self.timers.record_message();
+        self.loyalty.record_chat(&message.username);
+        if let Err(e) = self.storage.record_chat(&message.username, Utc::now()) {
+            error!("Failed to record chat stats: {}", e);
         }
     }
 
-    pub async fn execute(&self, command_name: &str, message: &TwitchMessage) -> Option<String> {
-        if let Some(command) = self.commands.get(command_name) {
-            command.execute(message).await
-        } else {
-            None
+    pub async fn dispatch_chat(&self, message: &TwitchMessage) -> Vec<String> {
+        let mut responses = Vec::new();
+        for listener in &self.listeners {
+            if let Some(response) = listener.on_message(message).await {
+                responses.push(response);
+            }
         }
+        responses
+    }
+
+    pub fn greet(&self, message: &TwitchMessage) -> Option<String> {
+        self.greeter.greet(message)
     }
 
-    pub async fn handle_spotify_reward(&self, message: &str) -> Option<String> {
-        self.spotify_service.add_track_from_url(message).await
+    pub async fn handle_spotify_reward(
+        &self,
+        user: &str,
+        message: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let track = self.spotify_service.queue_track_from_url(message).await?;
+        let play = SongPlay {
+            track: track.clone(),
+            requested_by: Some(user.to_lowercase()),
+            played_at: Utc::now(),
+        };
+        if let Err(e) = self.storage.record_song(&play) {
+            error!("Failed to record song history: {}", e);
+        }
+        Ok(track)
+    }
+
+    pub async fn handle_raid(&self, raider: &str) -> Option<String> {
+        if !self.config.shoutout.on_raid {
+            return None;
+        }
+
+        match self.shoutout_service.shoutout(raider).await {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Raid shoutout error: {}", e);
+                None
+            }
+        }
     }
 }
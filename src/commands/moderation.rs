@@ -0,0 +1,477 @@
+use async_trait::async_trait;
+use tracing::error;
+
+use super::Command;
+use crate::{
+    bot::TwitchMessage,
+    config::Permission,
+    moderation::{Action, BlockEdit, Blocklist, ChatFilter, ModerationService, NO_REASON},
+};
+
+fn parse_target(message: &TwitchMessage) -> Option<(String, Vec<&str>)> {
+    let mut args = message.arg_text().split_whitespace();
+    let login = args.next()?.trim_start_matches('@').to_lowercase();
+    Some((login, args.collect()))
+}
+
+fn reason_or_default(words: &[&str]) -> String {
+    if words.is_empty() {
+        NO_REASON.to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
+pub struct TimeoutCommand {
+    service: ModerationService,
+    default_duration: u32,
+}
+
+impl TimeoutCommand {
+    pub fn new(service: ModerationService, default_duration: u32) -> Self {
+        Self {
+            service,
+            default_duration,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for TimeoutCommand {
+    fn name(&self) -> &str {
+        "timeout"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["to"]
+    }
+
+    fn description(&self) -> &str {
+        "times someone out"
+    }
+
+    fn usage(&self) -> &str {
+        "<user> [seconds] [reason]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, rest) = parse_target(message)?;
+        let (duration, reason) = match rest.first().and_then(|d| d.parse::<u32>().ok()) {
+            Some(duration) => (duration, reason_or_default(&rest[1..])),
+            None => (self.default_duration, reason_or_default(&rest)),
+        };
+
+        let action = Action::Timeout(duration);
+        match self
+            .service
+            .apply(&message.username, &login, action, &reason)
+            .await
+        {
+            Ok(true) => Some(format!("timed out {} for {}s", login, duration)),
+            Ok(false) => Some(format!("couldn't find {}", login)),
+            Err(e) => {
+                error!("Timeout error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct BanCommand {
+    service: ModerationService,
+}
+
+impl BanCommand {
+    pub fn new(service: ModerationService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Command for BanCommand {
+    fn name(&self) -> &str {
+        "ban"
+    }
+
+    fn description(&self) -> &str {
+        "bans someone"
+    }
+
+    fn usage(&self) -> &str {
+        "<user> [reason]"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, rest) = parse_target(message)?;
+        let reason = reason_or_default(&rest);
+
+        match self
+            .service
+            .apply(&message.username, &login, Action::Ban, &reason)
+            .await
+        {
+            Ok(true) => Some(format!("banned {}", login)),
+            Ok(false) => Some(format!("couldn't find {}", login)),
+            Err(e) => {
+                error!("Ban error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct UnbanCommand {
+    service: ModerationService,
+}
+
+impl UnbanCommand {
+    pub fn new(service: ModerationService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Command for UnbanCommand {
+    fn name(&self) -> &str {
+        "unban"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["untimeout"]
+    }
+
+    fn description(&self) -> &str {
+        "unbans someone"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, rest) = parse_target(message)?;
+        let reason = reason_or_default(&rest);
+
+        match self
+            .service
+            .apply(&message.username, &login, Action::Unban, &reason)
+            .await
+        {
+            Ok(true) => Some(format!("unbanned {}", login)),
+            Ok(false) => Some(format!("couldn't find {}", login)),
+            Err(e) => {
+                error!("Unban error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct ClearCommand {
+    service: ModerationService,
+}
+
+impl ClearCommand {
+    pub fn new(service: ModerationService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Command for ClearCommand {
+    fn name(&self) -> &str {
+        "clear"
+    }
+
+    fn description(&self) -> &str {
+        "clears chat"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        match self.service.clear(&message.username).await {
+            Ok(()) => None,
+            Err(e) => {
+                error!("Clear error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct ModlogCommand {
+    service: ModerationService,
+}
+
+impl ModlogCommand {
+    pub fn new(service: ModerationService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Command for ModlogCommand {
+    fn name(&self) -> &str {
+        "modlog"
+    }
+
+    fn description(&self) -> &str {
+        "shows someone's moderation history"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, _) = parse_target(message)?;
+
+        match self.service.history(&login).await {
+            Ok(entries) if entries.is_empty() => Some(format!("no actions logged for {}", login)),
+            Ok(entries) => {
+                let summary = entries
+                    .iter()
+                    .map(|entry| {
+                        let action = match entry.duration {
+                            Some(seconds) => format!("{} {}s", entry.action, seconds),
+                            None => entry.action.clone(),
+                        };
+                        format!(
+                            "{} {} by {} ({})",
+                            entry.timestamp.format("%Y-%m-%d"),
+                            action,
+                            entry.moderator,
+                            entry.reason
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                Some(format!("{}: {}", login, summary))
+            }
+            Err(e) => {
+                error!("Modlog error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
+
+pub struct PermitCommand {
+    filter: ChatFilter,
+}
+
+impl PermitCommand {
+    pub fn new(filter: ChatFilter) -> Self {
+        Self { filter }
+    }
+}
+
+#[async_trait]
+impl Command for PermitCommand {
+    fn name(&self) -> &str {
+        "permit"
+    }
+
+    fn description(&self) -> &str {
+        "lets someone post one link"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, _) = parse_target(message)?;
+        let seconds = self.filter.permit(&login);
+        Some(format!(
+            "{} can post one link in the next {}s",
+            login, seconds
+        ))
+    }
+}
+
+pub struct BlockCommand {
+    blocklist: Blocklist,
+}
+
+impl BlockCommand {
+    pub fn new(blocklist: Blocklist) -> Self {
+        Self { blocklist }
+    }
+}
+
+#[async_trait]
+impl Command for BlockCommand {
+    fn name(&self) -> &str {
+        "block"
+    }
+
+    fn description(&self) -> &str {
+        "manages blocked terms"
+    }
+
+    fn usage(&self) -> &str {
+        "add|remove <term> or list"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (subcommand, term) = message
+            .arg_text()
+            .split_once(char::is_whitespace)
+            .map(|(subcommand, term)| (subcommand, term.trim()))
+            .unwrap_or((message.arg_text(), ""));
+
+        let edit = match subcommand {
+            "add" if !term.is_empty() => self.blocklist.add(term),
+            "remove" | "delete" if !term.is_empty() => self.blocklist.remove(term),
+            "list" => {
+                let terms = self.blocklist.list();
+                if terms.is_empty() {
+                    return Some("the blocklist is empty".to_string());
+                }
+                return Some(truncate_list(&terms));
+            }
+            _ => return Some("usage: !block add|remove <term> or !block list".to_string()),
+        };
+
+        let response = match edit {
+            BlockEdit::Added => format!("added {} to the blocklist", term),
+            BlockEdit::Removed => format!("removed {} from the blocklist", term),
+            BlockEdit::Exists => format!("{} is already blocked", term),
+            BlockEdit::Missing => format!("{} isn't on the blocklist", term),
+            BlockEdit::Protected => format!("{} is set in config.toml", term),
+            BlockEdit::Invalid => "that term is invalid".to_string(),
+        };
+        Some(response)
+    }
+}
+
+fn truncate_list(terms: &[String]) -> String {
+    let mut response = format!("blocked ({}):", terms.len());
+    for (shown, term) in terms.iter().enumerate() {
+        if response.len() + term.len() + 20 > 450 {
+            response.push_str(&format!(" and {} more", terms.len() - shown));
+            break;
+        }
+        response.push(' ');
+        response.push_str(term);
+    }
+    response
+}
+
+pub struct ApproveCommand {
+    filter: ChatFilter,
+    service: ModerationService,
+}
+
+impl ApproveCommand {
+    pub fn new(filter: ChatFilter, service: ModerationService) -> Self {
+        Self { filter, service }
+    }
+}
+
+#[async_trait]
+impl Command for ApproveCommand {
+    fn name(&self) -> &str {
+        "approve"
+    }
+
+    fn description(&self) -> &str {
+        "lets a held first-time chatter post links"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, _) = parse_target(message)?;
+        let Some(held) = self.filter.take_held(&login) else {
+            return Some(format!("no held message from {}", login));
+        };
+
+        if let Err(e) = self
+            .service
+            .apply_to(
+                &message.username,
+                &held.user_id,
+                &held.login,
+                Action::Unban,
+                "approved held message",
+            )
+            .await
+        {
+            error!("Approve error: {}", e);
+        }
+
+        Some(format!("{} said: {}", held.login, held.text))
+    }
+}
+
+pub struct DenyCommand {
+    filter: ChatFilter,
+}
+
+impl DenyCommand {
+    pub fn new(filter: ChatFilter) -> Self {
+        Self { filter }
+    }
+}
+
+#[async_trait]
+impl Command for DenyCommand {
+    fn name(&self) -> &str {
+        "deny"
+    }
+
+    fn description(&self) -> &str {
+        "keeps a held first-time chatter's link removed"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let (login, _) = parse_target(message)?;
+        match self.filter.take_held(&login) {
+            Some(_) => Some(format!("dropped {}'s held message", login)),
+            None => Some(format!("no held message from {}", login)),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+
+use super::{ChatListener, Command, format::parse_duration};
+use crate::{
+    bot::TwitchMessage,
+    polls::{Polls, Vote, parse_poll},
+};
+
+pub struct PollCommand {
+    polls: Polls,
+}
+
+impl PollCommand {
+    pub fn new(polls: Polls) -> Self {
+        Self { polls }
+    }
+}
+
+#[async_trait]
+impl Command for PollCommand {
+    fn name(&self) -> &str {
+        "poll"
+    }
+
+    fn description(&self) -> &str {
+        "shows the running poll, mods can start and end one"
+    }
+
+    fn usage(&self) -> &str {
+        "[duration] \"question\" option 1 | option 2, or end"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args = message.arg_text();
+
+        if args.is_empty() {
+            return Some(
+                self.polls
+                    .tally_message()
+                    .unwrap_or_else(|| "there's no poll running".to_string()),
+            );
+        }
+
+        if !message.is_moderator {
+            return Some("😭😂✌️".to_string());
+        }
+
+        if matches!(args.to_lowercase().as_str(), "end" | "stop") {
+            return Some(
+                self.polls
+                    .end()
+                    .await
+                    .unwrap_or_else(|| "there's no poll running".to_string()),
+            );
+        }
+
+        let (duration, rest) = match args.split_once(char::is_whitespace) {
+            Some((first, rest)) => match parse_duration(first) {
+                Some(duration) => (Some(duration), rest),
+                None => (None, args),
+            },
+            None => (None, args),
+        };
+
+        match parse_poll(rest) {
+            Some(spec) => Some(self.polls.start(spec, duration).await),
+            None => Some(r#"usage: !poll [duration] "question" option 1 | option 2"#.to_string()),
+        }
+    }
+}
+
+pub struct VoteCommand {
+    polls: Polls,
+}
+
+impl VoteCommand {
+    pub fn new(polls: Polls) -> Self {
+        Self { polls }
+    }
+}
+
+#[async_trait]
+impl Command for VoteCommand {
+    fn name(&self) -> &str {
+        "vote"
+    }
+
+    fn description(&self) -> &str {
+        "votes in the running poll"
+    }
+
+    fn usage(&self) -> &str {
+        "<number>"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let Ok(choice) = message.arg_text().trim_start_matches('#').parse::<usize>() else {
+            return Some("usage: !vote <number>".to_string());
+        };
+
+        match self.polls.vote(&message.username, choice) {
+            Vote::Counted | Vote::AlreadyVoted => None,
+            Vote::Invalid(options) => Some(format!(
+                "@{} pick a number from 1 to {}",
+                message.username, options
+            )),
+            Vote::NoPoll => Some("there's no poll running".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl ChatListener for Polls {
+    async fn on_message(&self, message: &TwitchMessage) -> Option<String> {
+        if let Ok(choice) = message.message.trim().parse::<usize>() {
+            self.vote(&message.username, choice);
+        }
+        None
+    }
+}
@@ -0,0 +1,150 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::CommandsConfig;
+
+pub struct Invocation<'a> {
+    pub text: &'a str,
+    pub mentioned: bool,
+}
+
+pub fn parse_invocation<'a>(
+    text: &'a str,
+    config: &CommandsConfig,
+    bot: &str,
+) -> Option<Invocation<'a>> {
+    let text = text.trim();
+
+    for prefix in config.prefixes.iter().filter(|prefix| !prefix.is_empty()) {
+        if let Some(rest) = text.strip_prefix(prefix.as_str()) {
+            let rest = rest.trim_start();
+            return (!rest.is_empty()).then_some(Invocation {
+                text: rest,
+                mentioned: false,
+            });
+        }
+    }
+
+    if !config.mention {
+        return None;
+    }
+
+    let rest = text.strip_prefix('@')?;
+    let end = rest
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    let (name, rest) = rest.split_at(end);
+    let rest = rest.trim_start_matches([',', ':']).trim_start();
+
+    (name.eq_ignore_ascii_case(bot) && !rest.is_empty()).then_some(Invocation {
+        text: rest,
+        mentioned: true,
+    })
+}
+
+pub fn display_prefix(config: &CommandsConfig) -> &str {
+    config
+        .prefixes
+        .iter()
+        .find(|prefix| !prefix.is_empty())
+        .map(String::as_str)
+        .unwrap_or("!")
+}
+
+pub fn normalize_name(name: &str) -> String {
+    name.nfkc().flat_map(char::to_lowercase).collect()
+}
+
+pub fn command_name(invocation: &str) -> String {
+    normalize_name(invocation.split_whitespace().next().unwrap_or(""))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let length = name.chars().count();
+    if length < 3 || !name.chars().all(char::is_alphanumeric) {
+        return None;
+    }
+
+    let limit = if length <= 4 { 1 } else { 2 };
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| (1..=limit).contains(distance))
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(prefixes: &[&str], mention: bool) -> CommandsConfig {
+        CommandsConfig {
+            prefixes: prefixes.iter().map(|prefix| prefix.to_string()).collect(),
+            mention,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn strips_any_configured_prefix() {
+        let config = config(&["!", "?"], false);
+
+        let invocation = parse_invocation("!song", &config, "bot").unwrap();
+        assert_eq!(invocation.text, "song");
+        assert!(!invocation.mentioned);
+        assert_eq!(
+            parse_invocation("? quote 3", &config, "bot").unwrap().text,
+            "quote 3"
+        );
+        assert!(parse_invocation("song", &config, "bot").is_none());
+        assert!(parse_invocation("!", &config, "bot").is_none());
+    }
+
+    #[test]
+    fn accepts_mentions_of_the_bot() {
+        let enabled = config(&["!"], true);
+        let disabled = config(&["!"], false);
+
+        let invocation = parse_invocation("@Bot, song", &enabled, "bot").unwrap();
+        assert_eq!(invocation.text, "song");
+        assert!(invocation.mentioned);
+        assert!(parse_invocation("@bottom song", &enabled, "bot").is_none());
+        assert!(parse_invocation("@bot", &enabled, "bot").is_none());
+        assert!(parse_invocation("@bot song", &disabled, "bot").is_none());
+    }
+
+    #[test]
+    fn normalizes_command_names() {
+        assert_eq!(command_name("Song please"), "song");
+        assert_eq!(command_name("ＳＰＯＴＩＦＹ"), "spotify");
+        assert_eq!(command_name(""), "");
+    }
+
+    #[test]
+    fn suggests_near_misses_only() {
+        let names = ["spotify", "song", "skip", "sens"];
+
+        assert_eq!(suggest("spotfy", names), Some("spotify"));
+        assert_eq!(suggest("sogn", names), None);
+        assert_eq!(suggest("sonh", names), Some("song"));
+        assert_eq!(suggest("song", names), None);
+        assert_eq!(suggest("xyzzy", names), None);
+        assert_eq!(suggest("sp", names), None);
+    }
+}
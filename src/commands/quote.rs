@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::error;
+
+use super::{Command, channel::current_game};
+use crate::{
+    bot::TwitchMessage,
+    config::Config,
+    error::Result,
+    helix::HelixClient,
+    quotes::QuoteBook,
+    storage::{NewQuote, Quote},
+};
+
+fn format_quote(quote: &Quote) -> String {
+    let context = match &quote.game {
+        Some(game) => format!("{}, {}", game, quote.date),
+        None => quote.date.to_string(),
+    };
+    format!(
+        "#{}: \"{}\" - {} ({})",
+        quote.id, quote.text, quote.author, context
+    )
+}
+
+fn parse_id(id: &str) -> std::result::Result<u32, std::num::ParseIntError> {
+    id.trim_start_matches('#').parse()
+}
+
+fn split_author(text: &str) -> (&str, Option<&str>) {
+    match text.rsplit_once(" - ") {
+        Some((text, author)) if !text.trim().is_empty() && !author.trim().is_empty() => {
+            (text.trim(), Some(author.trim().trim_start_matches('@')))
+        }
+        _ => (text.trim(), None),
+    }
+}
+
+pub struct QuoteCommand {
+    config: Arc<Config>,
+    helix: HelixClient,
+    quotes: QuoteBook,
+}
+
+impl QuoteCommand {
+    pub fn new(config: Arc<Config>, helix: HelixClient, quotes: QuoteBook) -> Self {
+        Self {
+            config,
+            helix,
+            quotes,
+        }
+    }
+
+    async fn game(&self) -> Option<String> {
+        if !self.helix.is_configured() {
+            return None;
+        }
+
+        match current_game(&self.helix).await {
+            Ok(game) => game,
+            Err(e) => {
+                error!("Failed to look up game for quote: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn add(&self, text: &str) -> Result<String> {
+        let (text, author) = split_author(text);
+        if text.is_empty() {
+            return Ok("usage: !quote add <text> [- author]".to_string());
+        }
+
+        let quote = self.quotes.add(NewQuote {
+            text: text.to_string(),
+            author: author.unwrap_or(&self.config.twitch.channel).to_string(),
+            game: self.game().await,
+            date: Utc::now().date_naive(),
+        })?;
+        Ok(format!("added quote #{}", quote.id))
+    }
+
+    fn edit(&self, rest: &str) -> Result<String> {
+        let (id, text) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (text, author) = split_author(text);
+        let Ok(id) = parse_id(id) else {
+            return Ok("usage: !quote edit <number> <text> [- author]".to_string());
+        };
+        if text.is_empty() {
+            return Ok("usage: !quote edit <number> <text> [- author]".to_string());
+        }
+
+        Ok(match self.quotes.edit(id, text, author)? {
+            Some(quote) => format!("updated {}", format_quote(&quote)),
+            None => format!("no quote #{}", id),
+        })
+    }
+
+    fn delete(&self, id: &str) -> Result<String> {
+        let Ok(id) = parse_id(id) else {
+            return Ok("usage: !quote delete <number>".to_string());
+        };
+
+        if self.quotes.delete(id)? {
+            Ok(format!("deleted quote #{}", id))
+        } else {
+            Ok(format!("no quote #{}", id))
+        }
+    }
+
+    fn get(&self, id: &str) -> Result<String> {
+        let Ok(id) = parse_id(id) else {
+            return Ok("usage: !quote [number|search <keyword>|add|edit|delete]".to_string());
+        };
+
+        Ok(match self.quotes.get(id)? {
+            Some(quote) => format_quote(&quote),
+            None => format!("no quote #{}", id),
+        })
+    }
+
+    fn random(&self) -> Result<String> {
+        Ok(match self.quotes.random()? {
+            Some(quote) => format_quote(&quote),
+            None => "no quotes yet".to_string(),
+        })
+    }
+
+    fn search(&self, keyword: &str) -> Result<String> {
+        if keyword.is_empty() {
+            return Ok("usage: !quote search <keyword>".to_string());
+        }
+
+        let matches = self.quotes.search(keyword)?;
+        Ok(match matches.as_slice() {
+            [] => format!("no quotes matching {}", keyword),
+            [quote] => format_quote(quote),
+            quotes => {
+                let ids = quotes
+                    .iter()
+                    .map(|quote| format!("#{}", quote.id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{} quotes match: {}", quotes.len(), ids)
+            }
+        })
+    }
+
+    fn export(&self) -> Result<String> {
+        let count = self.quotes.export()?;
+        Ok(format!(
+            "exported {} quotes to {}",
+            count,
+            self.quotes.export_path().display()
+        ))
+    }
+
+    fn import(&self) -> Result<String> {
+        let summary = self.quotes.import()?;
+        let mut response = format!(
+            "imported {} quotes from {}",
+            summary.imported,
+            self.quotes.export_path().display()
+        );
+        if !summary.collisions.is_empty() {
+            let ids: Vec<String> = summary
+                .collisions
+                .iter()
+                .map(|id| format!("#{}", id))
+                .collect();
+            response.push_str(&format!(", skipped {} (ids already used)", ids.join(", ")));
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Command for QuoteCommand {
+    fn name(&self) -> &str {
+        "quote"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["quotes"]
+    }
+
+    fn description(&self) -> &str {
+        "shows a quote, mods can add and edit them"
+    }
+
+    fn usage(&self) -> &str {
+        "[number|search <keyword>|add|edit|delete|import|export]"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args = message.arg_text();
+        let (subcommand, rest) = args
+            .split_once(char::is_whitespace)
+            .map(|(subcommand, rest)| (subcommand, rest.trim()))
+            .unwrap_or((args, ""));
+        let subcommand = subcommand.to_lowercase();
+
+        let is_mod_only = matches!(
+            subcommand.as_str(),
+            "add" | "edit" | "delete" | "del" | "remove" | "import" | "export"
+        );
+        if is_mod_only && !message.is_moderator {
+            return Some("😭😂✌️".to_string());
+        }
+
+        let result = match subcommand.as_str() {
+            "" | "random" => self.random(),
+            "add" => self.add(rest).await,
+            "edit" => self.edit(rest),
+            "delete" | "del" | "remove" => self.delete(rest),
+            "search" => self.search(rest),
+            "export" => self.export(),
+            "import" => self.import(),
+            id => self.get(id),
+        };
+
+        match result {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Quote error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
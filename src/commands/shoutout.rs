@@ -0,0 +1,141 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::{debug, error, warn};
+
+use super::Command;
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, Permission},
+    error::Result,
+    helix::HelixClient,
+    storage::SharedStorage,
+    template::Template,
+};
+
+const GLOBAL_COOLDOWN: Duration = Duration::from_secs(2 * 60);
+const TARGET_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone)]
+pub struct ShoutoutService {
+    config: Arc<Config>,
+    helix: HelixClient,
+    storage: SharedStorage,
+    last: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ShoutoutService {
+    pub fn new(config: Arc<Config>, helix: HelixClient, storage: SharedStorage) -> Self {
+        Self {
+            config,
+            helix,
+            storage,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn ready(&self, target: &str) -> Result<bool> {
+        let global = self
+            .last
+            .lock()
+            .unwrap()
+            .is_none_or(|last| last.elapsed() >= GLOBAL_COOLDOWN);
+        let target = self
+            .storage
+            .cooldown(&format!("shoutout:{}", target))?
+            .is_none_or(|last| (Utc::now() - last).to_std().unwrap_or_default() >= TARGET_COOLDOWN);
+        Ok(global && target)
+    }
+
+    fn record(&self, target: &str) -> Result<()> {
+        *self.last.lock().unwrap() = Some(Instant::now());
+        self.storage
+            .set_cooldown(&format!("shoutout:{}", target), Utc::now())
+    }
+
+    pub async fn shoutout(&self, login: &str) -> Result<Option<String>> {
+        let login = login.trim_start_matches('@').to_lowercase();
+
+        let Some(user) = self.helix.get_user(&login).await? else {
+            return Ok(None);
+        };
+
+        let game = self
+            .helix
+            .get_channel(&user.id)
+            .await?
+            .map(|channel| channel.game_name)
+            .filter(|game| !game.is_empty())
+            .unwrap_or_else(|| "nothing yet".to_string());
+
+        if self.ready(&user.id)? {
+            match self.helix.send_shoutout(&user.id).await {
+                Ok(()) => self.record(&user.id)?,
+                Err(e) => warn!("Native shoutout for {} failed: {}", user.login, e),
+            }
+        } else {
+            debug!("Native shoutout for {} is on cooldown", user.login);
+        }
+
+        let message =
+            Template::parse(&self.config.shoutout.template).render(|name, _| match name {
+                "name" => Some(user.display_name.clone()),
+                "login" => Some(user.login.clone()),
+                "game" => Some(game.clone()),
+                "link" => Some(format!("https://twitch.tv/{}", user.login)),
+                _ => None,
+            });
+
+        Ok(Some(message))
+    }
+}
+
+pub struct ShoutoutCommand {
+    service: ShoutoutService,
+}
+
+impl ShoutoutCommand {
+    pub fn new(service: ShoutoutService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Command for ShoutoutCommand {
+    fn name(&self) -> &str {
+        "so"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["shoutout"]
+    }
+
+    fn description(&self) -> &str {
+        "gives someone a shoutout"
+    }
+
+    fn usage(&self) -> &str {
+        "<user>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let target = message.arg_text().split_whitespace().next()?;
+
+        match self.service.shoutout(target).await {
+            Ok(Some(response)) => Some(response),
+            Ok(None) => Some(format!("couldn't find {}", target)),
+            Err(e) => {
+                error!("Shoutout error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
+    }
+}
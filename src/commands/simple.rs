@@ -1,16 +1,134 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
+use tracing::error;
+
+use super::{Command, channel::stream_uptime, spotify::SpotifyService};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, Permission},
+    counters::CounterStore,
+    helix::HelixClient,
+    template::{self, Template},
+};
 
-use super::Command;
-use crate::bot::TwitchMessage;
+#[derive(Clone)]
+pub struct TemplateContext {
+    pub config: Arc<Config>,
+    pub helix: HelixClient,
+    pub spotify: SpotifyService,
+    pub counters: CounterStore,
+}
 
 pub struct SimpleCommand {
     name: String,
-    response: String,
+    response: Template,
+    context: TemplateContext,
+    cooldown: Duration,
+    permission: Permission,
+    last_used: Mutex<Option<Instant>>,
 }
 
 impl SimpleCommand {
-    pub fn new(name: String, response: String) -> Self {
-        Self { name, response }
+    pub fn new(name: String, response: String, context: TemplateContext) -> Self {
+        Self {
+            name,
+            response: Template::parse(&response),
+            context,
+            cooldown: Duration::ZERO,
+            permission: Permission::Everyone,
+            last_used: Mutex::new(None),
+        }
+    }
+
+    pub fn with_limits(mut self, cooldown: u64, permission: Permission) -> Self {
+        self.cooldown = Duration::from_secs(cooldown);
+        self.permission = permission;
+        self
+    }
+
+    fn on_cooldown(&self, message: &TwitchMessage) -> bool {
+        if message.permission() >= Permission::Moderator {
+            return false;
+        }
+
+        let now = Instant::now();
+        let mut last_used = self.last_used.lock().unwrap();
+        if last_used.is_some_and(|used| now.duration_since(used) < self.cooldown) {
+            return true;
+        }
+        *last_used = Some(now);
+        false
+    }
+
+    pub async fn render(&self, user: &str, args: &[&str]) -> String {
+        let uptime = if self.response.uses("uptime") {
+            Some(self.uptime().await)
+        } else {
+            None
+        };
+        let song = if self.response.uses("song") {
+            Some(self.song().await)
+        } else {
+            None
+        };
+        let count = if self.response.uses("count") {
+            Some(self.context.counters.increment(&self.name).to_string())
+        } else {
+            None
+        };
+
+        let touser = args
+            .first()
+            .map(|arg| arg.trim_start_matches('@').to_string())
+            .unwrap_or_else(|| user.to_string());
+        let mut rng = rand::rng();
+
+        self.response.render(|name, arg| match name {
+            "user" => Some(user.to_string()),
+            "touser" => Some(touser.clone()),
+            "args" => Some(args.join(" ")),
+            "channel" => Some(self.context.config.twitch.channel.clone()),
+            "uptime" => uptime.clone(),
+            "song" => song.clone(),
+            "count" => count.clone(),
+            "counter" => Some(self.context.counters.get(arg?.trim()).to_string()),
+            index if index.chars().all(|c| c.is_ascii_digit()) => {
+                let index: usize = index.parse().ok()?;
+                Some(
+                    args.get(index.checked_sub(1)?)
+                        .copied()
+                        .unwrap_or("")
+                        .to_string(),
+                )
+            }
+            other => template::builtin(other, arg, &mut rng),
+        })
+    }
+
+    async fn uptime(&self) -> String {
+        match stream_uptime(&self.context.helix).await {
+            Ok(Some(uptime)) => uptime,
+            Ok(None) => "offline".to_string(),
+            Err(e) => {
+                error!("Uptime error: {}", e);
+                "unknown".to_string()
+            }
+        }
+    }
+
+    async fn song(&self) -> String {
+        match self.context.spotify.get_currently_playing().await {
+            Ok(Some(song)) => song,
+            Ok(None) => "nothing".to_string(),
+            Err(e) => {
+                error!("Spotify error: {}", e);
+                "unknown".to_string()
+            }
+        }
     }
 }
 
@@ -20,7 +138,16 @@ impl Command for SimpleCommand {
         &self.name
     }
 
-    async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
-        Some(self.response.clone())
+    fn permission(&self) -> Permission {
+        self.permission
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        if self.on_cooldown(message) {
+            return None;
+        }
+
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+        Some(self.render(&message.username, &args).await)
     }
 }
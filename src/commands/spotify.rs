@@ -2,11 +2,20 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use base64::{Engine, engine::general_purpose};
+use chrono::Utc;
 use serde::Deserialize;
+use thiserror::Error;
 use tracing::error;
 
 use super::Command;
-use crate::{bot::TwitchMessage, config::Config};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, Permission},
+    loyalty::Loyalty,
+    storage::{SharedStorage, SongPlay},
+};
+
+const RECENT_SONGS: usize = 5;
 
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
@@ -23,8 +32,16 @@ struct CurrentlyPlaying {
 struct Track {
     name: String,
     artists: Vec<Artist>,
+    #[serde(default)]
+    duration_ms: u64,
+    #[serde(default)]
+    explicit: bool,
 }
 
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct TrackRejected(String);
+
 #[derive(Debug, Deserialize)]
 struct Artist {
     name: String,
@@ -66,7 +83,9 @@ impl SpotifyService {
         Ok(token_response.access_token)
     }
 
-    async fn get_currently_playing(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    pub async fn get_currently_playing(
+        &self,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
         let access_token = self.get_access_token().await?;
 
         let response = self
@@ -148,12 +167,13 @@ impl SpotifyService {
 
         let track_response = self
             .client
-            .get(&format!("https://api.spotify.com/v1/tracks/{}", track_id))
+            .get(format!("https://api.spotify.com/v1/tracks/{}", track_id))
             .header("Authorization", format!("Bearer {}", access_token))
             .send()
             .await?;
 
         let track: Track = track_response.json().await?;
+        self.check_track(&track)?;
         let artists = track
             .artists
             .iter()
@@ -168,7 +188,8 @@ impl SpotifyService {
             .query(&[("uri", uri)])
             .json(&serde_json::json!({}))
             .send()
-            .await?;
+            .await?
+            .error_for_status()?;
 
         Ok(format!(
             "{} by {}",
@@ -177,18 +198,61 @@ impl SpotifyService {
         ))
     }
 
-    pub async fn add_track_from_url(&self, url: &str) -> Option<String> {
-        let track_id = url.split("/track/").nth(1)?.split('?').next()?;
+    fn check_track(&self, track: &Track) -> Result<(), TrackRejected> {
+        let requests = &self.config.spotify.requests;
+
+        if requests.max_duration > 0 && track.duration_ms / 1000 > requests.max_duration {
+            return Err(TrackRejected(format!(
+                "that song is too long, the limit is {} minutes",
+                requests.max_duration / 60
+            )));
+        }
+
+        if track.explicit && !requests.allow_explicit {
+            return Err(TrackRejected("no explicit songs please".to_string()));
+        }
+
+        let blocked = track.artists.iter().any(|artist| {
+            requests
+                .blocked_artists
+                .iter()
+                .any(|blocked| blocked.eq_ignore_ascii_case(&artist.name))
+        });
+        if blocked {
+            return Err(TrackRejected("that artist isn't allowed".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn queue_track_from_url(
+        &self,
+        url: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let track_id = url
+            .split("/track/")
+            .nth(1)
+            .and_then(|rest| rest.split('?').next())
+            .ok_or("not a spotify track link")?;
 
         let uri = format!("spotify:track:{}", track_id);
+        self.add_to_queue(&uri).await
+    }
 
-        match self.add_to_queue(&uri).await {
-            Ok(track_info) => Some(format!("{} has been added to the queue :3", track_info)),
-            Err(e) => {
-                error!("Failed to add track: {}", e);
-                Some("😭😂✌️".to_string())
-            }
+    pub async fn add_track_from_url(&self, url: &str) -> Result<String, String> {
+        if !url.contains("/track/") {
+            return Err("that's not a spotify track link".to_string());
         }
+
+        self.queue_track_from_url(url)
+            .await
+            .map_err(|e| match e.downcast_ref::<TrackRejected>() {
+                Some(rejected) => rejected.to_string(),
+                None => {
+                    error!("Failed to add track: {}", e);
+                    "😭😂✌️".to_string()
+                }
+            })
     }
 }
 
@@ -212,6 +276,10 @@ impl Command for SpotifyCommand {
         vec!["song", "music", "np", "nowplaying"]
     }
 
+    fn description(&self) -> &str {
+        "shows the song that's playing"
+    }
+
     async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
         match self.service.get_currently_playing().await {
             Ok(response) => response,
@@ -239,17 +307,163 @@ impl Command for PlayCommand {
         "play"
     }
 
-    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
-        if !message.has_permissions() {
-            return Some("😭😂✌️".to_string());
-        }
+    fn description(&self) -> &str {
+        "queues a spotify track"
+    }
+
+    fn usage(&self) -> &str {
+        "<spotify track link>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Vip
+    }
 
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
         let parts: Vec<&str> = message.message.split_whitespace().collect();
         if parts.len() < 2 {
             return Some("😭😂✌️".to_string());
         }
 
-        self.service.add_track_from_url(parts[1]).await
+        match self.service.add_track_from_url(parts[1]).await {
+            Ok(track) => Some(format!("{} has been added to the queue :3", track)),
+            Err(reason) => Some(reason),
+        }
+    }
+}
+
+pub struct SongsCommand {
+    storage: SharedStorage,
+}
+
+impl SongsCommand {
+    pub fn new(storage: SharedStorage) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl Command for SongsCommand {
+    fn name(&self) -> &str {
+        "songs"
+    }
+
+    fn description(&self) -> &str {
+        "shows the last requested songs"
+    }
+
+    async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
+        let plays = match self.storage.recent_songs(RECENT_SONGS) {
+            Ok(plays) => plays,
+            Err(e) => {
+                error!("Song history error: {}", e);
+                return Some("😭😂✌️".to_string());
+            }
+        };
+        if plays.is_empty() {
+            return Some("no songs have been requested yet".to_string());
+        }
+
+        let songs: Vec<String> = plays
+            .into_iter()
+            .map(|play| match play.requested_by {
+                Some(login) => format!("{} ({})", play.track, login),
+                None => play.track,
+            })
+            .collect();
+        Some(format!("recent songs: {}", songs.join(", ")))
+    }
+}
+
+pub struct SongRequestCommand {
+    config: Arc<Config>,
+    service: SpotifyService,
+    loyalty: Loyalty,
+    storage: SharedStorage,
+}
+
+impl SongRequestCommand {
+    pub fn new(
+        config: Arc<Config>,
+        service: SpotifyService,
+        loyalty: Loyalty,
+        storage: SharedStorage,
+    ) -> Self {
+        Self {
+            config,
+            service,
+            loyalty,
+            storage,
+        }
+    }
+
+    async fn request(&self, login: &str, url: &str) -> crate::error::Result<String> {
+        let cost = self.config.spotify.requests.cost;
+
+        if cost > 0 && !self.loyalty.spend(login, cost)? {
+            let points = self.loyalty.points(login)?;
+            return Ok(format!(
+                "@{} a song request costs {} points, you have {}",
+                login, cost, points
+            ));
+        }
+
+        match self.service.add_track_from_url(url).await {
+            Ok(track) => {
+                let response = format!("{} has been added to the queue :3", track);
+                let play = SongPlay {
+                    track,
+                    requested_by: Some(login.to_string()),
+                    played_at: Utc::now(),
+                };
+                if let Err(e) = self.storage.record_song(&play) {
+                    error!("Failed to record song history: {}", e);
+                }
+                Ok(response)
+            }
+            Err(reason) if cost > 0 => {
+                self.loyalty.give(login, cost)?;
+                Ok(format!("{} (refunded {} points)", reason, cost))
+            }
+            Err(reason) => Ok(reason),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for SongRequestCommand {
+    fn name(&self) -> &str {
+        "sr"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["songrequest"]
+    }
+
+    fn description(&self) -> &str {
+        "requests a song with points"
+    }
+
+    fn usage(&self) -> &str {
+        "<spotify track link>"
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let Some(url) = message.arg_text().split_whitespace().next() else {
+            return Some(format!(
+                "usage: !sr <spotify track link> ({} points)",
+                self.config.spotify.requests.cost
+            ));
+        };
+
+        let login = message.username.to_lowercase();
+        match self.request(&login, url).await {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("Song request error: {}", e);
+                Some("😭😂✌️".to_string())
+            }
+        }
     }
 }
 
@@ -273,11 +487,15 @@ impl Command for SkipCommand {
         vec!["next"]
     }
 
-    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
-        if !message.has_permissions() {
-            return Some("😭😂✌️".to_string());
-        }
+    fn description(&self) -> &str {
+        "skips the current song"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Vip
+    }
 
+    async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
         match self.service.skip_track().await {
             Ok(track_info) => Some(format!("skipped to {}", track_info)),
             Err(e) => {
@@ -308,11 +526,15 @@ impl Command for PrevCommand {
         vec!["previous", "back"]
     }
 
-    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
-        if !message.has_permissions() {
-            return Some("😭😂✌️".to_string());
-        }
+    fn description(&self) -> &str {
+        "goes back to the previous song"
+    }
 
+    fn permission(&self) -> Permission {
+        Permission::Vip
+    }
+
+    async fn execute(&self, _message: &TwitchMessage) -> Option<String> {
         match self.service.previous_track().await {
             Ok(track_info) => Some(format!("went back to {}", track_info)),
             Err(e) => {
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+
+use super::Command;
+use crate::{bot::TwitchMessage, config::Permission, timers::Timers};
+
+pub struct TimerCommand {
+    timers: Timers,
+}
+
+impl TimerCommand {
+    pub fn new(timers: Timers) -> Self {
+        Self { timers }
+    }
+}
+
+#[async_trait]
+impl Command for TimerCommand {
+    fn name(&self) -> &str {
+        "timer"
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec!["timers"]
+    }
+
+    fn description(&self) -> &str {
+        "lists or toggles timers"
+    }
+
+    fn usage(&self) -> &str {
+        "list or enable|disable <name>"
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Moderator
+    }
+
+    async fn execute(&self, message: &TwitchMessage) -> Option<String> {
+        let args: Vec<&str> = message.arg_text().split_whitespace().collect();
+
+        match args.as_slice() {
+            ["enable" | "on", name] => Some(if self.timers.set_enabled(name, true) {
+                format!("timer {} enabled", name)
+            } else {
+                format!("no timer called {}", name)
+            }),
+            ["disable" | "off", name] => Some(if self.timers.set_enabled(name, false) {
+                format!("timer {} disabled", name)
+            } else {
+                format!("no timer called {}", name)
+            }),
+            [] | ["list"] => {
+                let timers = self.timers.list();
+                if timers.is_empty() {
+                    return Some("no timers configured".to_string());
+                }
+
+                let list = timers
+                    .iter()
+                    .map(|(name, enabled, interval)| {
+                        let state = if *enabled { "on" } else { "off" };
+                        format!("{} ({}m, {})", name, interval, state)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("timers: {}", list))
+            }
+            _ => Some("usage: !timer list or !timer enable|disable <name>".to_string()),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use tracing::{debug, warn};
+
+use super::{
+    ChatListener, Command,
+    custom::CommandTable,
+    prefix::parse_invocation,
+    simple::{SimpleCommand, TemplateContext},
+};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, TriggerConfig},
+};
+
+fn words(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn matches_keyword(text: &str, keyword: &str) -> bool {
+    let keyword = words(keyword);
+    !keyword.is_empty() && format!(" {} ", words(text)).contains(&format!(" {} ", keyword))
+}
+
+#[derive(Clone)]
+enum Responder {
+    Command(String),
+    Response(Arc<dyn Command>),
+}
+
+struct Trigger {
+    name: String,
+    config: TriggerConfig,
+    regex: Option<Regex>,
+    responder: Responder,
+    last_fired: Option<Instant>,
+    last_fired_by: HashMap<String, Instant>,
+}
+
+impl Trigger {
+    fn matches(&self, text: &str) -> bool {
+        self.config
+            .keywords
+            .iter()
+            .any(|keyword| matches_keyword(text, keyword))
+            || self
+                .regex
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(text))
+    }
+
+    fn ready(&self, login: &str, now: Instant) -> bool {
+        let once_per = Duration::from_secs(self.config.once_per * 60);
+        let cooldown = Duration::from_secs(self.config.cooldown);
+
+        self.last_fired
+            .is_none_or(|fired| now.duration_since(fired) >= once_per)
+            && self
+                .last_fired_by
+                .get(login)
+                .is_none_or(|fired| now.duration_since(*fired) >= cooldown)
+    }
+}
+
+#[derive(Clone)]
+pub struct Triggers {
+    config: Arc<Config>,
+    table: CommandTable,
+    triggers: Arc<Mutex<Vec<Trigger>>>,
+}
+
+impl Triggers {
+    pub fn new(config: Arc<Config>, table: CommandTable, context: TemplateContext) -> Self {
+        let mut triggers: Vec<Trigger> = config
+            .triggers
+            .iter()
+            .filter(|(_, trigger)| trigger.enabled)
+            .filter_map(|(name, trigger)| Self::build(name, trigger, &context))
+            .collect();
+        triggers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            config,
+            table,
+            triggers: Arc::new(Mutex::new(triggers)),
+        }
+    }
+
+    fn build(name: &str, config: &TriggerConfig, context: &TemplateContext) -> Option<Trigger> {
+        let regex = match &config.regex {
+            Some(pattern) => match RegexBuilder::new(pattern).case_insensitive(true).build() {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("Trigger {} has an invalid regex: {}", name, e);
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        if config.keywords.is_empty() && regex.is_none() {
+            warn!("Trigger {} has no keywords or regex", name);
+            return None;
+        }
+
+        let responder = match (&config.response, &config.command) {
+            (Some(response), _) => Responder::Response(Arc::new(SimpleCommand::new(
+                format!("trigger:{}", name),
+                response.clone(),
+                context.clone(),
+            ))),
+            (None, Some(command)) => Responder::Command(command.to_lowercase()),
+            (None, None) => {
+                warn!("Trigger {} has no response or command", name);
+                return None;
+            }
+        };
+
+        Some(Trigger {
+            name: name.to_string(),
+            config: config.clone(),
+            regex,
+            responder,
+            last_fired: None,
+            last_fired_by: HashMap::new(),
+        })
+    }
+
+    fn claim(&self, message: &TwitchMessage) -> Option<Responder> {
+        let now = Instant::now();
+        let login = message.username.to_lowercase();
+        let mut triggers = self.triggers.lock().unwrap();
+
+        let trigger = triggers.iter_mut().find(|trigger| {
+            message.permission() >= trigger.config.permission
+                && trigger.matches(&message.message)
+                && trigger.ready(&login, now)
+        })?;
+
+        trigger.last_fired = Some(now);
+        trigger.last_fired_by.insert(login, now);
+        debug!("Trigger {} fired", trigger.name);
+        Some(trigger.responder.clone())
+    }
+}
+
+#[async_trait]
+impl ChatListener for Triggers {
+    async fn on_message(&self, message: &TwitchMessage) -> Option<String> {
+        if parse_invocation(
+            &message.message,
+            &self.config.commands,
+            &self.config.twitch.username,
+        )
+        .is_some()
+        {
+            return None;
+        }
+
+        let command = match self.claim(message)? {
+            Responder::Response(command) => command,
+            Responder::Command(name) => match self.table.read().unwrap().get(&name).cloned() {
+                Some(command) => command,
+                None => {
+                    warn!("Trigger points at unknown command {}", name);
+                    return None;
+                }
+            },
+        };
+        if message.permission() < command.permission() {
+            return None;
+        }
+
+        command.execute(message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_words_case_insensitively() {
+        assert!(matches_keyword("What DPI do you use?", "what dpi"));
+        assert!(matches_keyword("dpi?", "dpi"));
+        assert!(!matches_keyword("whatever dpi", "what dpi"));
+        assert!(!matches_keyword("hdpi screen", "dpi"));
+    }
+
+    #[test]
+    fn ignores_empty_keywords() {
+        assert!(!matches_keyword("anything", ""));
+        assert!(!matches_keyword("anything", "?!"));
+    }
+}
@@ -6,7 +6,36 @@ use serde::Deserialize;
 pub struct Config {
     pub twitch: TwitchConfig,
     pub spotify: SpotifyConfig,
+    #[serde(default)]
     pub commands: CommandsConfig,
+    #[serde(default)]
+    pub helix: HelixConfig,
+    #[serde(default)]
+    pub eventsub: EventSubConfig,
+    #[serde(default)]
+    pub shoutout: ShoutoutConfig,
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+    #[serde(default)]
+    pub greetings: GreetingsConfig,
+    #[serde(default)]
+    pub timers: HashMap<String, TimerConfig>,
+    #[serde(default)]
+    pub counters: CountersConfig,
+    #[serde(default)]
+    pub quotes: QuotesConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub loyalty: LoyaltyConfig,
+    #[serde(default)]
+    pub giveaway: GiveawayConfig,
+    #[serde(default)]
+    pub polls: PollsConfig,
+    #[serde(default)]
+    pub games: GamesConfig,
+    #[serde(default)]
+    pub triggers: HashMap<String, TriggerConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,11 +51,544 @@ pub struct SpotifyConfig {
     pub client_secret: String,
     pub refresh_token: String,
     pub reward_id: String,
+    #[serde(default)]
+    pub requests: SongRequestConfig,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SongRequestConfig {
+    pub cost: i64,
+    pub max_duration: u64,
+    pub allow_explicit: bool,
+    pub blocked_artists: Vec<String>,
+}
+
+impl Default for SongRequestConfig {
+    fn default() -> Self {
+        Self {
+            cost: 100,
+            max_duration: 600,
+            allow_explicit: true,
+            blocked_artists: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
 pub struct CommandsConfig {
-    pub simple: HashMap<String, String>,
+    pub simple: HashMap<String, SimpleCommandConfig>,
+    pub aliases: HashMap<String, String>,
+    pub prefixes: Vec<String>,
+    pub mention: bool,
+    pub suggestions: bool,
+    pub list_file: Option<String>,
+}
+
+impl Default for CommandsConfig {
+    fn default() -> Self {
+        Self {
+            simple: HashMap::new(),
+            aliases: HashMap::new(),
+            prefixes: vec!["!".to_string()],
+            mention: true,
+            suggestions: true,
+            list_file: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "SimpleCommandEntry")]
+pub struct SimpleCommandConfig {
+    pub response: String,
+    pub aliases: Vec<String>,
+    pub cooldown: u64,
+    pub permission: Permission,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SimpleCommandEntry {
+    Response(String),
+    Table {
+        response: String,
+        #[serde(default)]
+        aliases: Vec<String>,
+        #[serde(default)]
+        cooldown: u64,
+        #[serde(default)]
+        permission: Permission,
+    },
+}
+
+impl From<SimpleCommandEntry> for SimpleCommandConfig {
+    fn from(entry: SimpleCommandEntry) -> Self {
+        match entry {
+            SimpleCommandEntry::Response(response) => Self {
+                response,
+                aliases: Vec::new(),
+                cooldown: 0,
+                permission: Permission::Everyone,
+            },
+            SimpleCommandEntry::Table {
+                response,
+                aliases,
+                cooldown,
+                permission,
+            } => Self {
+                response,
+                aliases,
+                cooldown,
+                permission,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HelixConfig {
+    pub base_url: String,
+    pub auth_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+impl Default for HelixConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.twitch.tv/helix".to_string(),
+            auth_url: "https://id.twitch.tv/oauth2".to_string(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            access_token: String::new(),
+            refresh_token: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct EventSubConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub subscriptions_url: Option<String>,
+}
+
+impl Default for EventSubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "wss://eventsub.wss.twitch.tv/ws".to_string(),
+            subscriptions_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ShoutoutConfig {
+    pub template: String,
+    pub on_raid: bool,
+}
+
+impl Default for ShoutoutConfig {
+    fn default() -> Self {
+        Self {
+            template: "go follow {name} at {link} they were last playing {game} :3".to_string(),
+            on_raid: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    pub audit_log: String,
+    pub default_timeout: u32,
+    pub permit_seconds: u64,
+    pub hold_first_links: bool,
+    pub first_link_timeout: u32,
+    pub escalation: Vec<u32>,
+    pub escalation_reset: u64,
+    pub links: LinkFilterConfig,
+    pub caps: CapsFilterConfig,
+    pub symbols: SymbolFilterConfig,
+    pub emotes: EmoteFilterConfig,
+    pub length: LengthFilterConfig,
+    pub repeats: RepeatFilterConfig,
+    pub blocklist: BlocklistConfig,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            audit_log: "data/audit.log".to_string(),
+            default_timeout: 600,
+            permit_seconds: 60,
+            hold_first_links: false,
+            first_link_timeout: 60,
+            escalation: vec![10, 60, 600],
+            escalation_reset: 3600,
+            links: LinkFilterConfig::default(),
+            caps: CapsFilterConfig::default(),
+            symbols: SymbolFilterConfig::default(),
+            emotes: EmoteFilterConfig::default(),
+            length: LengthFilterConfig::default(),
+            repeats: RepeatFilterConfig::default(),
+            blocklist: BlocklistConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    Delete,
+    Timeout,
+    Warn,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct LinkFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub allowed_domains: Vec<String>,
+}
+
+impl Default for LinkFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Delete,
+            allowed_domains: vec!["twitch.tv".to_string(), "clips.twitch.tv".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct CapsFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub min_letters: usize,
+    pub max_ratio: f64,
+}
+
+impl Default for CapsFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Warn,
+            min_letters: 15,
+            max_ratio: 0.7,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SymbolFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub min_length: usize,
+    pub max_ratio: f64,
+}
+
+impl Default for SymbolFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Warn,
+            min_length: 15,
+            max_ratio: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct EmoteFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub max_count: usize,
+}
+
+impl Default for EmoteFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Delete,
+            max_count: 15,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct LengthFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub max_length: usize,
+}
+
+impl Default for LengthFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Delete,
+            max_length: 400,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RepeatFilterConfig {
+    pub enabled: bool,
+    pub action: FilterAction,
+    pub max_repeats: usize,
+    pub window: u64,
+}
+
+impl Default for RepeatFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: FilterAction::Timeout,
+            max_repeats: 3,
+            window: 60,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct BlocklistConfig {
+    pub enabled: bool,
+    pub terms_file: String,
+    pub words: Vec<String>,
+    pub regexes: Vec<String>,
+}
+
+impl Default for BlocklistConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            terms_file: "data/blocklist.txt".to_string(),
+            words: Vec::new(),
+            regexes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct GreetingsConfig {
+    pub enabled: bool,
+    pub first_time: String,
+    pub returning: String,
+}
+
+impl Default for GreetingsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            first_time: "welcome to the stream {user} :3".to_string(),
+            returning: "welcome back {user} :3".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimerConfig {
+    pub interval: u64,
+    #[serde(default)]
+    pub min_messages: u64,
+    pub command: Option<String>,
+    pub message: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub announce: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    #[default]
+    Everyone,
+    Subscriber,
+    Vip,
+    Moderator,
+    Broadcaster,
+}
+
+impl Permission {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Everyone => "everyone",
+            Self::Subscriber => "subs",
+            Self::Vip => "vips",
+            Self::Moderator => "mods",
+            Self::Broadcaster => "broadcaster",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriggerConfig {
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    pub regex: Option<String>,
+    pub command: Option<String>,
+    pub response: Option<String>,
+    #[serde(default)]
+    pub cooldown: u64,
+    #[serde(default)]
+    pub once_per: u64,
+    #[serde(default)]
+    pub permission: Permission,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CountersConfig {
+    pub export_dir: Option<String>,
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct QuotesConfig {
+    pub export_file: String,
+}
+
+impl Default for QuotesConfig {
+    fn default() -> Self {
+        Self {
+            export_file: "data/quotes-export.json".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub path: String,
+    pub legacy_dir: String,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            path: "data/bot.db".to_string(),
+            legacy_dir: "data".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct LoyaltyConfig {
+    pub enabled: bool,
+    pub interval: u64,
+    pub points_per_interval: i64,
+    pub active_bonus: i64,
+    pub active_window: u64,
+    pub ignored: Vec<String>,
+}
+
+impl Default for LoyaltyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 5,
+            points_per_interval: 10,
+            active_bonus: 5,
+            active_window: 10,
+            ignored: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct GiveawayConfig {
+    pub subscriber_luck: u32,
+    pub followers_only: bool,
+    pub min_watch_time: u64,
+    pub exclude_recent_days: u64,
+    pub log_file: String,
+    pub seed: Option<u64>,
+}
+
+impl Default for GiveawayConfig {
+    fn default() -> Self {
+        Self {
+            subscriber_luck: 1,
+            followers_only: false,
+            min_watch_time: 0,
+            exclude_recent_days: 0,
+            log_file: "data/giveaways.jsonl".to_string(),
+            seed: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct PollsConfig {
+    pub duration: u64,
+    pub tally_interval: u64,
+    pub mirror_native: bool,
+}
+
+impl Default for PollsConfig {
+    fn default() -> Self {
+        Self {
+            duration: 120,
+            tally_interval: 30,
+            mirror_native: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct GamesConfig {
+    pub timeout: u64,
+    pub reward: i64,
+    pub trivia_file: String,
+    pub words: Vec<String>,
+    pub guess_max: u32,
+    pub duel_timeout: u64,
+    pub min_wager: i64,
+}
+
+impl Default for GamesConfig {
+    fn default() -> Self {
+        Self {
+            timeout: 60,
+            reward: 50,
+            trivia_file: "data/trivia.json".to_string(),
+            words: Vec::new(),
+            guess_max: 100,
+            duel_timeout: 60,
+            min_wager: 10,
+        }
+    }
 }
 
 impl Config {
@@ -36,3 +598,37 @@ impl Config {
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simple_commands_as_strings_or_tables() {
+        let commands: CommandsConfig = toml::from_str(
+            r#"
+            [simple]
+            dpi = "i use 800 dpi"
+            mouse = { response = "op1 8k", aliases = ["specs"], cooldown = 30, permission = "vip" }
+
+            [aliases]
+            gear = "mouse"
+            "#,
+        )
+        .unwrap();
+
+        let dpi = &commands.simple["dpi"];
+        assert_eq!(dpi.response, "i use 800 dpi");
+        assert!(dpi.aliases.is_empty());
+        assert_eq!(dpi.permission, Permission::Everyone);
+
+        let mouse = &commands.simple["mouse"];
+        assert_eq!(mouse.response, "op1 8k");
+        assert_eq!(mouse.aliases, vec!["specs"]);
+        assert_eq!(mouse.cooldown, 30);
+        assert_eq!(mouse.permission, Permission::Vip);
+
+        assert_eq!(commands.aliases["gear"], "mouse");
+        assert_eq!(commands.prefixes, vec!["!"]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::BTreeSet, fs, path::PathBuf, sync::Arc};
+
+use tracing::error;
+
+use crate::{config::CountersConfig, error::Result, storage::SharedStorage};
+
+pub enum CounterEdit {
+    Created,
+    Removed,
+    Exists,
+    Missing,
+    Protected,
+}
+
+#[derive(Clone)]
+pub struct CounterStore {
+    storage: SharedStorage,
+    export_dir: Option<PathBuf>,
+    config_names: Arc<BTreeSet<String>>,
+}
+
+impl CounterStore {
+    pub fn new(config: &CountersConfig, storage: SharedStorage) -> Self {
+        let store = Self {
+            storage,
+            export_dir: config.export_dir.as_ref().map(PathBuf::from),
+            config_names: Arc::new(config.names.iter().map(|n| n.to_lowercase()).collect()),
+        };
+
+        for name in store.counters() {
+            let value = store.get(&name);
+            store.export(&name, value);
+        }
+
+        store
+    }
+
+    pub fn counters(&self) -> Vec<String> {
+        let created = self.storage.created_counters().unwrap_or_else(|e| {
+            error!("Failed to load counters: {}", e);
+            Vec::new()
+        });
+
+        self.config_names
+            .iter()
+            .cloned()
+            .chain(created)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> i64 {
+        self.storage.counter_value(name).unwrap_or_else(|e| {
+            error!("Failed to read counter {}: {}", name, e);
+            0
+        })
+    }
+
+    pub fn increment(&self, name: &str) -> i64 {
+        self.adjust(name, 1)
+    }
+
+    pub fn adjust(&self, name: &str, delta: i64) -> i64 {
+        self.update(name, self.storage.adjust_counter_value(name, delta))
+    }
+
+    pub fn set(&self, name: &str, value: i64) -> i64 {
+        let result = self.storage.set_counter_value(name, value).map(|_| value);
+        self.update(name, result)
+    }
+
+    pub fn create(&self, name: &str) -> CounterEdit {
+        let name = name.to_lowercase();
+        if self.config_names.contains(&name) {
+            return CounterEdit::Exists;
+        }
+
+        match self.storage.create_counter(&name) {
+            Ok(true) => {
+                self.export(&name, self.get(&name));
+                CounterEdit::Created
+            }
+            Ok(false) => CounterEdit::Exists,
+            Err(e) => {
+                error!("Failed to create counter {}: {}", name, e);
+                CounterEdit::Missing
+            }
+        }
+    }
+
+    pub fn remove(&self, name: &str) -> CounterEdit {
+        let name = name.to_lowercase();
+        if self.config_names.contains(&name) {
+            return CounterEdit::Protected;
+        }
+
+        match self.storage.delete_counter(&name) {
+            Ok(true) => CounterEdit::Removed,
+            Ok(false) => CounterEdit::Missing,
+            Err(e) => {
+                error!("Failed to remove counter {}: {}", name, e);
+                CounterEdit::Missing
+            }
+        }
+    }
+
+    fn update(&self, name: &str, result: Result<i64>) -> i64 {
+        let name = name.to_lowercase();
+        match result {
+            Ok(value) => {
+                if self.counters().contains(&name) {
+                    self.export(&name, value);
+                }
+                value
+            }
+            Err(e) => {
+                error!("Failed to update counter {}: {}", name, e);
+                self.get(&name)
+            }
+        }
+    }
+
+    fn export(&self, name: &str, value: i64) {
+        let Some(dir) = &self.export_dir else {
+            return;
+        };
+
+        let result = fs::create_dir_all(dir)
+            .and_then(|_| fs::write(dir.join(format!("{}.txt", name)), value.to_string()));
+
+        if let Err(e) = result {
+            error!("Failed to export counter {}: {}", name, e);
+        }
+    }
+}
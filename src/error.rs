@@ -4,19 +4,43 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum BotError {
     #[error("WebSocket error: {0}")]
-    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
 
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
 
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] rusqlite::Error),
+
     #[error("Config error: {0}")]
     Config(String),
 
     #[error("Spotify API error: {status}: {message}")]
     SpotifyApi { status: u16, message: String },
 
+    #[error("Helix API error: {status}: {message}")]
+    HelixApi { status: u16, message: String },
+
+    #[error("Missing OAuth scope: {0}")]
+    MissingScope(String),
+
+    #[error("EventSub error: {0}")]
+    EventSub(String),
+
     #[error("Authentication failed")]
     AuthenticationFailed,
 }
 
+impl From<tokio_tungstenite::tungstenite::Error> for BotError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(error))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, BotError>;
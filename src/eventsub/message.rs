@@ -0,0 +1,69 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct WebSocketMessage {
+    pub metadata: Metadata,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Metadata {
+    pub message_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionPayload {
+    pub session: Session,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub keepalive_timeout_seconds: Option<u64>,
+    pub reconnect_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationPayload {
+    pub subscription: Subscription,
+    #[serde(default)]
+    pub event: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Subscription {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Redemption {
+    pub id: String,
+    pub user_login: String,
+    #[serde(default)]
+    pub user_input: String,
+    pub reward: Reward,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Reward {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Raid {
+    pub from_broadcaster_user_login: String,
+    pub viewers: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum EventSubEvent {
+    Redemption(Redemption),
+    Raid(Raid),
+    StreamOnline,
+    StreamOffline,
+}
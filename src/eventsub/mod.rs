@@ -0,0 +1,312 @@
+pub mod message;
+
+use std::{sync::Arc, time::Duration};
+
+use futures_util::StreamExt;
+pub use message::EventSubEvent;
+use message::{NotificationPayload, SessionPayload, WebSocketMessage};
+use serde_json::json;
+use tokio::{net::TcpStream, sync::mpsc, time::timeout};
+use tokio_tungstenite::{
+    MaybeTlsStream, WebSocketStream, connect_async, tungstenite::protocol::Message,
+};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    config::Config,
+    error::{BotError, Result},
+    helix::HelixClient,
+};
+
+type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+const WELCOME_TIMEOUT: Duration = Duration::from_secs(10);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+pub struct EventSubClient {
+    config: Arc<Config>,
+    helix: HelixClient,
+    events: mpsc::UnboundedSender<EventSubEvent>,
+}
+
+impl EventSubClient {
+    pub fn new(
+        config: Arc<Config>,
+        helix: HelixClient,
+        events: mpsc::UnboundedSender<EventSubEvent>,
+    ) -> Self {
+        Self {
+            config,
+            helix,
+            events,
+        }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(async move { self.run().await });
+    }
+
+    async fn run(self) {
+        loop {
+            match self.session().await {
+                Ok(()) => warn!("EventSub connection closed, reconnecting"),
+                Err(e) => error!("EventSub error: {}", e),
+            }
+
+            if self.events.is_closed() {
+                break;
+            }
+
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+    }
+
+    async fn session(&self) -> Result<()> {
+        info!("Connecting to EventSub");
+
+        let (ws, session) = connect(&self.config.eventsub.url).await?;
+        self.subscribe(&session.session.id).await?;
+        listen(ws, keepalive_window(&session), &self.events).await
+    }
+
+    async fn subscribe(&self, session_id: &str) -> Result<()> {
+        let broadcaster_id = self.helix.broadcaster_id().await?;
+
+        let subscriptions = [
+            (
+                "channel.channel_points_custom_reward_redemption.add",
+                json!({ "broadcaster_user_id": broadcaster_id }),
+            ),
+            (
+                "channel.raid",
+                json!({ "to_broadcaster_user_id": broadcaster_id }),
+            ),
+            (
+                "stream.online",
+                json!({ "broadcaster_user_id": broadcaster_id }),
+            ),
+            (
+                "stream.offline",
+                json!({ "broadcaster_user_id": broadcaster_id }),
+            ),
+        ];
+
+        for (kind, condition) in subscriptions {
+            match self
+                .helix
+                .create_eventsub_subscription(kind, condition, session_id)
+                .await
+            {
+                Ok(()) => info!("Subscribed to {}", kind),
+                Err(e) => error!("Failed to subscribe to {}: {}", kind, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn connect(url: &str) -> Result<(WebSocket, SessionPayload)> {
+    let (mut ws, _) = connect_async(url).await?;
+
+    loop {
+        let frame = timeout(WELCOME_TIMEOUT, ws.next())
+            .await
+            .map_err(|_| BotError::EventSub("no welcome message".to_string()))?;
+
+        match frame {
+            Some(Ok(Message::Text(text))) => {
+                let message: WebSocketMessage = serde_json::from_str(&text)?;
+                if message.metadata.message_type == "session_welcome" {
+                    let session: SessionPayload = serde_json::from_value(message.payload)?;
+                    info!("EventSub session established: {}", session.session.id);
+                    return Ok((ws, session));
+                }
+            }
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+            None => {
+                return Err(BotError::EventSub(
+                    "connection closed before welcome".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+async fn listen(
+    mut ws: WebSocket,
+    mut keepalive: Duration,
+    events: &mpsc::UnboundedSender<EventSubEvent>,
+) -> Result<()> {
+    loop {
+        let frame = timeout(keepalive, ws.next())
+            .await
+            .map_err(|_| BotError::EventSub("keepalive timed out".to_string()))?;
+
+        let text = match frame {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => return Ok(()),
+            Some(Err(e)) => return Err(e.into()),
+            _ => continue,
+        };
+
+        debug!("EventSub received: {}", text);
+        let message: WebSocketMessage = serde_json::from_str(&text)?;
+
+        match message.metadata.message_type.as_str() {
+            "session_keepalive" => {}
+            "notification" => {
+                let payload: NotificationPayload = serde_json::from_value(message.payload)?;
+                dispatch(events, payload)?;
+            }
+            "session_reconnect" => {
+                let payload: SessionPayload = serde_json::from_value(message.payload)?;
+                let url = payload.session.reconnect_url.ok_or_else(|| {
+                    BotError::EventSub("reconnect message without url".to_string())
+                })?;
+
+                info!("EventSub requested reconnect");
+                let (new_ws, session) = connect(&url).await?;
+                ws = new_ws;
+                keepalive = keepalive_window(&session);
+            }
+            "revocation" => {
+                let payload: NotificationPayload = serde_json::from_value(message.payload)?;
+                warn!(
+                    "EventSub subscription {} revoked: {}",
+                    payload.subscription.kind, payload.subscription.status
+                );
+            }
+            other => debug!("Unhandled EventSub message type: {}", other),
+        }
+    }
+}
+
+fn dispatch(
+    events: &mpsc::UnboundedSender<EventSubEvent>,
+    payload: NotificationPayload,
+) -> serde_json::Result<()> {
+    let event = match payload.subscription.kind.as_str() {
+        "channel.channel_points_custom_reward_redemption.add" => {
+            EventSubEvent::Redemption(serde_json::from_value(payload.event)?)
+        }
+        "channel.raid" => EventSubEvent::Raid(serde_json::from_value(payload.event)?),
+        "stream.online" => EventSubEvent::StreamOnline,
+        "stream.offline" => EventSubEvent::StreamOffline,
+        other => {
+            debug!("Unhandled EventSub notification: {}", other);
+            return Ok(());
+        }
+    };
+
+    let _ = events.send(event);
+    Ok(())
+}
+
+fn keepalive_window(session: &SessionPayload) -> Duration {
+    let seconds = session.session.keepalive_timeout_seconds.unwrap_or(10);
+    Duration::from_secs(seconds + 5)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::SinkExt;
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    fn frame(message_type: &str, payload: Value) -> String {
+        json!({ "metadata": { "message_type": message_type }, "payload": payload }).to_string()
+    }
+
+    fn welcome(id: &str) -> String {
+        frame(
+            "session_welcome",
+            json!({ "session": { "id": id, "keepalive_timeout_seconds": 10 } }),
+        )
+    }
+
+    fn subscription(kind: &str, status: &str) -> Value {
+        json!({ "subscription": { "type": kind, "status": status }, "event": {} })
+    }
+
+    async fn serve(frames: Vec<String>, close: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            for frame in frames {
+                ws.send(Message::Text(frame.into())).await.unwrap();
+            }
+            if close {
+                let _ = ws.close(None).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn waits_for_the_welcome_message() {
+        let keepalive = frame("session_keepalive", json!({}));
+        let url = serve(vec![keepalive, welcome("first")], false).await;
+
+        let (_, session) = connect(&url).await.unwrap();
+        assert_eq!(session.session.id, "first");
+        assert_eq!(keepalive_window(&session), Duration::from_secs(15));
+
+        let url = serve(vec![], true).await;
+        assert!(matches!(connect(&url).await, Err(BotError::EventSub(_))));
+    }
+
+    #[tokio::test]
+    async fn times_out_without_keepalives() {
+        let url = serve(vec![welcome("quiet")], false).await;
+        let (ws, _) = connect(&url).await.unwrap();
+        let (events, _rx) = mpsc::unbounded_channel();
+
+        let result = listen(ws, Duration::from_millis(100), &events).await;
+        assert!(matches!(result, Err(BotError::EventSub(e)) if e == "keepalive timed out"));
+    }
+
+    #[tokio::test]
+    async fn follows_reconnect_messages() {
+        let online = frame("notification", subscription("stream.online", "enabled"));
+        let second = serve(vec![welcome("second"), online], true).await;
+        let reconnect = frame(
+            "session_reconnect",
+            json!({ "session": { "id": "first", "reconnect_url": second } }),
+        );
+        let first = serve(vec![welcome("first"), reconnect], false).await;
+
+        let (ws, _) = connect(&first).await.unwrap();
+        let (events, mut rx) = mpsc::unbounded_channel();
+        listen(ws, Duration::from_secs(5), &events).await.unwrap();
+
+        assert!(matches!(rx.try_recv(), Ok(EventSubEvent::StreamOnline)));
+    }
+
+    #[tokio::test]
+    async fn keeps_listening_after_revocation() {
+        let revoked = frame(
+            "revocation",
+            subscription("channel.raid", "authorization_revoked"),
+        );
+        let offline = frame("notification", subscription("stream.offline", "enabled"));
+        let url = serve(vec![welcome("first"), revoked, offline], true).await;
+
+        let (ws, _) = connect(&url).await.unwrap();
+        let (events, mut rx) = mpsc::unbounded_channel();
+        listen(ws, Duration::from_secs(5), &events).await.unwrap();
+
+        assert!(matches!(rx.try_recv(), Ok(EventSubEvent::StreamOffline)));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+};
+use tracing::{error, warn};
+
+use crate::{
+    bot::TwitchMessage, config::Config, error::Result, helix::HelixClient, storage::SharedStorage,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub login: String,
+    pub user_id: String,
+    pub subscriber: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DrawRecord {
+    timestamp: DateTime<Utc>,
+    keyword: String,
+    winner: String,
+    entries: usize,
+    reroll: bool,
+}
+
+struct Active {
+    keyword: String,
+    entries: Vec<Entry>,
+    closes_at: Option<Instant>,
+    winners: HashSet<String>,
+}
+
+pub enum Draw {
+    Winner { login: String, entries: usize },
+    NoEligible,
+    CheckFailed(usize),
+    NotRunning,
+}
+
+pub fn pick<R: Rng + ?Sized>(
+    entries: &[Entry],
+    subscriber_luck: u32,
+    rng: &mut R,
+) -> Option<usize> {
+    let weight = |entry: &Entry| {
+        if entry.subscriber {
+            subscriber_luck.max(1) as u64
+        } else {
+            1
+        }
+    };
+
+    let total: u64 = entries.iter().map(weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = rng.random_range(0..total);
+    for (index, entry) in entries.iter().enumerate() {
+        let weight = weight(entry);
+        if roll < weight {
+            return Some(index);
+        }
+        roll -= weight;
+    }
+
+    None
+}
+
+#[derive(Clone)]
+pub struct Giveaway {
+    config: Arc<Config>,
+    helix: HelixClient,
+    storage: SharedStorage,
+    log_path: PathBuf,
+    active: Arc<Mutex<Option<Active>>>,
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl Giveaway {
+    pub fn new(config: Arc<Config>, helix: HelixClient, storage: SharedStorage) -> Self {
+        let rng = match config.giveaway.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+
+        Self {
+            log_path: PathBuf::from(&config.giveaway.log_file),
+            config,
+            helix,
+            storage,
+            active: Arc::new(Mutex::new(None)),
+            rng: Arc::new(Mutex::new(rng)),
+        }
+    }
+
+    pub fn start(&self, keyword: &str, duration: Option<Duration>) {
+        *self.active.lock().unwrap() = Some(Active {
+            keyword: keyword.to_lowercase(),
+            entries: Vec::new(),
+            closes_at: duration.map(|duration| Instant::now() + duration),
+            winners: HashSet::new(),
+        });
+    }
+
+    pub fn cancel(&self) -> bool {
+        self.active.lock().unwrap().take().is_some()
+    }
+
+    pub fn status(&self) -> Option<(String, usize, bool)> {
+        let active = self.active.lock().unwrap();
+        active.as_ref().map(|active| {
+            let open = active
+                .closes_at
+                .is_none_or(|closes| Instant::now() < closes);
+            (active.keyword.clone(), active.entries.len(), open)
+        })
+    }
+
+    pub fn enter(&self, message: &TwitchMessage) {
+        let mut active = self.active.lock().unwrap();
+        let Some(active) = active.as_mut() else {
+            return;
+        };
+
+        if !message.message.trim().eq_ignore_ascii_case(&active.keyword)
+            || active
+                .closes_at
+                .is_some_and(|closes| Instant::now() >= closes)
+        {
+            return;
+        }
+
+        let login = message.username.to_lowercase();
+        if active.entries.iter().any(|entry| entry.login == login) {
+            return;
+        }
+
+        active.entries.push(Entry {
+            login,
+            user_id: message.user_id.clone(),
+            subscriber: message.is_subscriber,
+        });
+    }
+
+    pub async fn draw(&self, reroll: bool) -> Result<Draw> {
+        let (keyword, mut candidates, total) = {
+            let mut active = self.active.lock().unwrap();
+            let Some(active) = active.as_mut() else {
+                return Ok(Draw::NotRunning);
+            };
+
+            active.closes_at = Some(Instant::now());
+            if !reroll {
+                active.winners.clear();
+            }
+
+            let candidates: Vec<Entry> = active
+                .entries
+                .iter()
+                .filter(|entry| !active.winners.contains(&entry.login))
+                .cloned()
+                .collect();
+            (active.keyword.clone(), candidates, active.entries.len())
+        };
+
+        let recent = self.recent_winners().await;
+        candidates.retain(|entry| !recent.contains(&entry.login));
+
+        let mut unchecked = 0;
+        loop {
+            let index = {
+                let mut rng = self.rng.lock().unwrap();
+                pick(&candidates, self.config.giveaway.subscriber_luck, &mut *rng)
+            };
+            let Some(index) = index else {
+                return Ok(if unchecked > 0 {
+                    Draw::CheckFailed(unchecked)
+                } else {
+                    Draw::NoEligible
+                });
+            };
+
+            let entry = candidates.swap_remove(index);
+            match self.is_eligible(&entry).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    warn!("Skipping giveaway entry {}: {}", entry.login, e);
+                    unchecked += 1;
+                    continue;
+                }
+            }
+
+            if let Some(active) = self.active.lock().unwrap().as_mut() {
+                active.winners.insert(entry.login.clone());
+            }
+
+            let record = DrawRecord {
+                timestamp: Utc::now(),
+                keyword,
+                winner: entry.login.clone(),
+                entries: total,
+                reroll,
+            };
+            if let Err(e) = self.log(&record).await {
+                error!("Failed to log giveaway draw: {}", e);
+            }
+
+            return Ok(Draw::Winner {
+                login: entry.login,
+                entries: total,
+            });
+        }
+    }
+
+    async fn is_eligible(&self, entry: &Entry) -> Result<bool> {
+        let config = &self.config.giveaway;
+
+        if config.min_watch_time > 0
+            && self.storage.watch_time(&entry.login)? < config.min_watch_time * 60
+        {
+            return Ok(false);
+        }
+
+        if config.followers_only {
+            if entry.user_id.is_empty() {
+                return Ok(false);
+            }
+            if self.helix.get_follower(&entry.user_id).await?.is_none() {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn recent_winners(&self) -> HashSet<String> {
+        let days = self.config.giveaway.exclude_recent_days;
+        if days == 0 {
+            return HashSet::new();
+        }
+
+        let contents = match fs::read_to_string(&self.log_path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Couldn't read giveaway log: {}", e);
+                }
+                return HashSet::new();
+            }
+        };
+
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+        contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<DrawRecord>(line).ok())
+            .filter(|record| record.timestamp >= cutoff)
+            .map(|record| record.winner)
+            .collect()
+    }
+
+    async fn log(&self, record: &DrawRecord) -> std::io::Result<()> {
+        if let Some(parent) = self.log_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .await?;
+        file.write_all(line.as_bytes()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(login: &str, subscriber: bool) -> Entry {
+        Entry {
+            login: login.to_string(),
+            user_id: String::new(),
+            subscriber,
+        }
+    }
+
+    #[test]
+    fn empty_pool_has_no_winner() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(pick(&[], 1, &mut rng), None);
+    }
+
+    #[test]
+    fn same_seed_picks_same_winner() {
+        let entries: Vec<Entry> = (0..50)
+            .map(|i| entry(&format!("user{}", i), false))
+            .collect();
+        let first = pick(&entries, 1, &mut StdRng::seed_from_u64(42));
+        let second = pick(&entries, 1, &mut StdRng::seed_from_u64(42));
+        assert!(first.is_some());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn every_entry_can_win() {
+        let entries = vec![entry("a", false), entry("b", false), entry("c", false)];
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut seen = HashSet::new();
+        for _ in 0..200 {
+            seen.insert(pick(&entries, 1, &mut rng).unwrap());
+        }
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn subscriber_luck_weights_the_draw() {
+        let entries = vec![entry("sub", true), entry("viewer", false)];
+        let mut rng = StdRng::seed_from_u64(3);
+        let sub_wins = (0..4000)
+            .filter(|_| pick(&entries, 3, &mut rng) == Some(0))
+            .count();
+        assert!((2700..3300).contains(&sub_wins), "{}", sub_wins);
+    }
+
+    #[test]
+    fn luck_of_zero_counts_as_one() {
+        let entries = vec![entry("sub", true)];
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(pick(&entries, 0, &mut rng), Some(0));
+    }
+}
@@ -0,0 +1,296 @@
+use reqwest::Method;
+use serde_json::{Map, Value, json};
+
+use super::{
+    HelixClient,
+    auth::TokenKind,
+    types::{
+        Category, ChannelInfo, Chatter, DataResponse, Follower, Poll, RedemptionStatus, Stream,
+        User,
+    },
+};
+use crate::error::{BotError, Result};
+
+impl HelixClient {
+    pub async fn get_users(&self, logins: &[&str]) -> Result<Vec<User>> {
+        let query: Vec<_> = logins.iter().map(|login| ("login", *login)).collect();
+        self.get_data("users", &query, TokenKind::App).await
+    }
+
+    pub async fn get_user(&self, login: &str) -> Result<Option<User>> {
+        Ok(self.get_users(&[login]).await?.into_iter().next())
+    }
+
+    pub async fn get_stream(&self, user_id: &str) -> Result<Option<Stream>> {
+        let streams: Vec<Stream> = self
+            .get_data("streams", &[("user_id", user_id)], TokenKind::App)
+            .await?;
+        Ok(streams.into_iter().next())
+    }
+
+    pub async fn get_channel(&self, broadcaster_id: &str) -> Result<Option<ChannelInfo>> {
+        let channels: Vec<ChannelInfo> = self
+            .get_data(
+                "channels",
+                &[("broadcaster_id", broadcaster_id)],
+                TokenKind::App,
+            )
+            .await?;
+        Ok(channels.into_iter().next())
+    }
+
+    pub async fn modify_channel(&self, title: Option<&str>, game_id: Option<&str>) -> Result<()> {
+        self.tokens.require_scope("channel:manage:broadcast")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+
+        let mut body = Map::new();
+        if let Some(title) = title {
+            body.insert("title".to_string(), json!(title));
+        }
+        if let Some(game_id) = game_id {
+            body.insert("game_id".to_string(), json!(game_id));
+        }
+
+        self.send_json(
+            Method::PATCH,
+            "channels",
+            &[("broadcaster_id", &broadcaster_id)],
+            Some(Value::Object(body)),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_category(&self, name: &str) -> Result<Option<Category>> {
+        let games: Vec<Category> = self
+            .get_data("games", &[("name", name)], TokenKind::App)
+            .await?;
+        Ok(games.into_iter().next())
+    }
+
+    pub async fn search_categories(&self, query: &str) -> Result<Vec<Category>> {
+        self.get_data("search/categories", &[("query", query)], TokenKind::App)
+            .await
+    }
+
+    pub async fn get_follower(&self, user_id: &str) -> Result<Option<Follower>> {
+        self.tokens.require_scope("moderator:read:followers")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+
+        let followers: Vec<Follower> = self
+            .get_data(
+                "channels/followers",
+                &[("broadcaster_id", &broadcaster_id), ("user_id", user_id)],
+                TokenKind::User,
+            )
+            .await?;
+        Ok(followers.into_iter().next())
+    }
+
+    pub async fn ban_user(&self, user_id: &str, duration: Option<u32>, reason: &str) -> Result<()> {
+        self.tokens.require_scope("moderator:manage:banned_users")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        let mut data = json!({ "user_id": user_id, "reason": reason });
+        if let Some(duration) = duration {
+            data["duration"] = json!(duration);
+        }
+
+        self.send_json(
+            Method::POST,
+            "moderation/bans",
+            &[
+                ("broadcaster_id", &broadcaster_id),
+                ("moderator_id", &moderator_id),
+            ],
+            Some(json!({ "data": data })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unban_user(&self, user_id: &str) -> Result<()> {
+        self.tokens.require_scope("moderator:manage:banned_users")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        self.send_json(
+            Method::DELETE,
+            "moderation/bans",
+            &[
+                ("broadcaster_id", &broadcaster_id),
+                ("moderator_id", &moderator_id),
+                ("user_id", user_id),
+            ],
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_chat_messages(&self, message_id: Option<&str>) -> Result<()> {
+        self.tokens
+            .require_scope("moderator:manage:chat_messages")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        let mut query = vec![
+            ("broadcaster_id", broadcaster_id.as_str()),
+            ("moderator_id", moderator_id.as_str()),
+        ];
+        if let Some(message_id) = message_id {
+            query.push(("message_id", message_id));
+        }
+
+        self.send_json(Method::DELETE, "moderation/chat", &query, None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_chatters(&self) -> Result<Vec<Chatter>> {
+        self.tokens.require_scope("moderator:read:chatters")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        self.get_paginated(
+            "chat/chatters",
+            &[
+                ("broadcaster_id", &broadcaster_id),
+                ("moderator_id", &moderator_id),
+            ],
+            TokenKind::User,
+        )
+        .await
+    }
+
+    pub async fn send_announcement(&self, message: &str) -> Result<()> {
+        self.tokens
+            .require_scope("moderator:manage:announcements")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        self.send_json(
+            Method::POST,
+            "chat/announcements",
+            &[
+                ("broadcaster_id", &broadcaster_id),
+                ("moderator_id", &moderator_id),
+            ],
+            Some(json!({ "message": message })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_shoutout(&self, to_broadcaster_id: &str) -> Result<()> {
+        self.tokens.require_scope("moderator:manage:shoutouts")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let moderator_id = self.moderator_id().await?;
+
+        self.send_json(
+            Method::POST,
+            "chat/shoutouts",
+            &[
+                ("from_broadcaster_id", &broadcaster_id),
+                ("to_broadcaster_id", to_broadcaster_id),
+                ("moderator_id", &moderator_id),
+            ],
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_redemption_status(
+        &self,
+        reward_id: &str,
+        redemption_id: &str,
+        status: RedemptionStatus,
+    ) -> Result<()> {
+        self.tokens.require_scope("channel:manage:redemptions")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+
+        self.send_json(
+            Method::PATCH,
+            "channel_points/custom_rewards/redemptions",
+            &[
+                ("broadcaster_id", &broadcaster_id),
+                ("reward_id", reward_id),
+                ("id", redemption_id),
+            ],
+            Some(json!({ "status": status })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn create_poll(
+        &self,
+        title: &str,
+        choices: &[String],
+        duration: u64,
+    ) -> Result<Poll> {
+        self.tokens.require_scope("channel:manage:polls")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+        let choices: Vec<Value> = choices
+            .iter()
+            .map(|choice| json!({ "title": choice }))
+            .collect();
+
+        let response = self
+            .send_json(
+                Method::POST,
+                "polls",
+                &[],
+                Some(json!({
+                    "broadcaster_id": broadcaster_id,
+                    "title": title,
+                    "choices": choices,
+                    "duration": duration,
+                })),
+            )
+            .await?;
+        Self::first_poll(response.json::<DataResponse<Poll>>().await?.data)
+    }
+
+    pub async fn end_poll(&self, poll_id: &str) -> Result<Poll> {
+        self.tokens.require_scope("channel:manage:polls")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+
+        let response = self
+            .send_json(
+                Method::PATCH,
+                "polls",
+                &[],
+                Some(json!({
+                    "broadcaster_id": broadcaster_id,
+                    "id": poll_id,
+                    "status": "TERMINATED",
+                })),
+            )
+            .await?;
+        Self::first_poll(response.json::<DataResponse<Poll>>().await?.data)
+    }
+
+    pub async fn get_poll(&self, poll_id: &str) -> Result<Poll> {
+        self.tokens.require_scope("channel:manage:polls")?;
+        let broadcaster_id = self.broadcaster_id().await?;
+
+        let polls: Vec<Poll> = self
+            .get_data(
+                "polls",
+                &[("broadcaster_id", &broadcaster_id), ("id", poll_id)],
+                TokenKind::User,
+            )
+            .await?;
+        Self::first_poll(polls)
+    }
+
+    fn first_poll(polls: Vec<Poll>) -> Result<Poll> {
+        polls.into_iter().next().ok_or(BotError::HelixApi {
+            status: 200,
+            message: "poll response had no data".to_string(),
+        })
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{
+    config::Config,
+    error::{BotError, Result},
+};
+
+pub const VALIDATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    User,
+    App,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenInfo {
+    pub login: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<u64>,
+}
+
+struct AppToken {
+    token: String,
+    expires_at: Instant,
+}
+
+pub struct TokenManager {
+    config: Arc<Config>,
+    client: reqwest::Client,
+    user_token: RwLock<String>,
+    refresh_token: RwLock<String>,
+    app_token: RwLock<Option<AppToken>>,
+    info: RwLock<Option<TokenInfo>>,
+}
+
+impl TokenManager {
+    pub fn new(config: Arc<Config>, client: reqwest::Client) -> Self {
+        let user_token = RwLock::new(config.helix.access_token.clone());
+        let refresh_token = RwLock::new(config.helix.refresh_token.clone());
+
+        Self {
+            config,
+            client,
+            user_token,
+            refresh_token,
+            app_token: RwLock::new(None),
+            info: RwLock::new(None),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        !self.config.helix.client_id.is_empty() && !self.user_token().is_empty()
+    }
+
+    pub fn user_token(&self) -> String {
+        self.user_token.read().unwrap().clone()
+    }
+
+    pub fn info(&self) -> Option<TokenInfo> {
+        self.info.read().unwrap().clone()
+    }
+
+    fn can_refresh(&self) -> bool {
+        !self.config.helix.client_secret.is_empty()
+            && !self.refresh_token.read().unwrap().is_empty()
+    }
+
+    pub async fn bearer(&self, kind: TokenKind) -> Result<String> {
+        if kind == TokenKind::App && !self.config.helix.client_secret.is_empty() {
+            return self.app_token().await;
+        }
+        Ok(self.user_token())
+    }
+
+    pub fn require_scope(&self, scope: &str) -> Result<()> {
+        match &*self.info.read().unwrap() {
+            Some(info) if !info.scopes.iter().any(|s| s == scope) => {
+                Err(BotError::MissingScope(scope.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TokenInfo> {
+        match self.fetch_token_info().await {
+            Err(BotError::AuthenticationFailed) if self.can_refresh() => {
+                warn!("Helix user token is invalid, refreshing");
+                self.refresh().await?;
+                self.fetch_token_info().await
+            }
+            result => result,
+        }
+    }
+
+    async fn fetch_token_info(&self) -> Result<TokenInfo> {
+        let response = self
+            .client
+            .get(format!("{}/validate", self.config.helix.auth_url))
+            .header("Authorization", format!("OAuth {}", self.user_token()))
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(BotError::AuthenticationFailed);
+        }
+
+        let info: TokenInfo = response.error_for_status()?.json().await?;
+        info!(
+            "Helix token valid for {} ({}s remaining)",
+            info.login, info.expires_in
+        );
+        *self.info.write().unwrap() = Some(info.clone());
+        Ok(info)
+    }
+
+    pub async fn refresh(&self) -> Result<()> {
+        if !self.can_refresh() {
+            return Err(BotError::AuthenticationFailed);
+        }
+
+        let refresh_token = self.refresh_token.read().unwrap().clone();
+        let response = self
+            .client
+            .post(format!("{}/token", self.config.helix.auth_url))
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+                ("client_id", self.config.helix.client_id.as_str()),
+                ("client_secret", self.config.helix.client_secret.as_str()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(BotError::AuthenticationFailed);
+        }
+
+        let token: TokenResponse = response.json().await?;
+        *self.user_token.write().unwrap() = token.access_token;
+        if let Some(refresh_token) = token.refresh_token {
+            *self.refresh_token.write().unwrap() = refresh_token;
+        }
+
+        info!("Refreshed Helix user token");
+        Ok(())
+    }
+
+    async fn app_token(&self) -> Result<String> {
+        if let Some(token) = &*self.app_token.read().unwrap()
+            && token.expires_at > Instant::now()
+        {
+            return Ok(token.token.clone());
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/token", self.config.helix.auth_url))
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.config.helix.client_id.as_str()),
+                ("client_secret", self.config.helix.client_secret.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let token: TokenResponse = response.json().await?;
+        let lifetime = Duration::from_secs(token.expires_in.unwrap_or(3600));
+        let expires_at = Instant::now() + lifetime.saturating_sub(Duration::from_secs(60));
+
+        *self.app_token.write().unwrap() = Some(AppToken {
+            token: token.access_token.clone(),
+            expires_at,
+        });
+
+        info!("Fetched Helix app token");
+        Ok(token.access_token)
+    }
+}
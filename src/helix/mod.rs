@@ -0,0 +1,281 @@
+mod api;
+pub mod auth;
+pub mod types;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use auth::{TokenKind, TokenManager, VALIDATION_INTERVAL};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, header::HeaderMap};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tokio::sync::OnceCell;
+use tracing::{debug, error, warn};
+use types::DataResponse;
+pub use types::RedemptionStatus;
+
+use crate::{
+    config::Config,
+    error::{BotError, Result},
+};
+
+const MAX_RETRIES: u32 = 3;
+const PAGE_SIZE: &str = "100";
+
+#[derive(Debug, Default)]
+struct RateLimit {
+    remaining: Option<u64>,
+    reset: Option<u64>,
+}
+
+#[derive(Clone)]
+pub struct HelixClient {
+    config: Arc<Config>,
+    client: reqwest::Client,
+    tokens: Arc<TokenManager>,
+    rate_limit: Arc<Mutex<RateLimit>>,
+    broadcaster_id: Arc<OnceCell<String>>,
+}
+
+impl HelixClient {
+    pub fn new(config: Arc<Config>) -> Self {
+        let client = reqwest::Client::new();
+        let tokens = Arc::new(TokenManager::new(Arc::clone(&config), client.clone()));
+
+        Self {
+            config,
+            client,
+            tokens,
+            rate_limit: Arc::new(Mutex::new(RateLimit::default())),
+            broadcaster_id: Arc::new(OnceCell::new()),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.tokens.is_configured()
+    }
+
+    pub async fn validate(&self) -> Result<()> {
+        self.tokens.validate().await?;
+        Ok(())
+    }
+
+    pub fn spawn_validation(&self) {
+        let tokens = Arc::clone(&self.tokens);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(VALIDATION_INTERVAL);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = tokens.validate().await {
+                    error!("Helix token validation failed: {}", e);
+                }
+            }
+        });
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.config.helix.base_url, path)
+    }
+
+    async fn request(&self, method: Method, url: &str, kind: TokenKind) -> Result<RequestBuilder> {
+        let token = self.tokens.bearer(kind).await?;
+        Ok(self
+            .client
+            .request(method, url)
+            .header("Client-Id", &self.config.helix.client_id)
+            .bearer_auth(token))
+    }
+
+    async fn send<F>(
+        &self,
+        method: Method,
+        url: &str,
+        kind: TokenKind,
+        build: F,
+    ) -> Result<Response>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let mut refreshed = false;
+        let mut attempts = 0;
+
+        loop {
+            self.wait_for_rate_limit().await;
+
+            let request = build(self.request(method.clone(), url, kind).await?);
+            let response = request.send().await?;
+            self.update_rate_limit(response.headers());
+
+            match response.status() {
+                StatusCode::UNAUTHORIZED if kind == TokenKind::User && !refreshed => {
+                    warn!("Helix request unauthorized, refreshing token");
+                    self.tokens.refresh().await?;
+                    refreshed = true;
+                }
+                StatusCode::TOO_MANY_REQUESTS if attempts < MAX_RETRIES => {
+                    warn!("Helix rate limit hit, retrying");
+                    attempts += 1;
+                }
+                status if !status.is_success() => {
+                    let message = response.text().await.unwrap_or_default();
+                    return Err(BotError::HelixApi {
+                        status: status.as_u16(),
+                        message,
+                    });
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    async fn wait_for_rate_limit(&self) {
+        let wait = {
+            let rate_limit = self.rate_limit.lock().unwrap();
+            match (rate_limit.remaining, rate_limit.reset) {
+                (Some(0), Some(reset)) => reset.saturating_sub(unix_now()),
+                _ => 0,
+            }
+        };
+
+        if wait > 0 {
+            debug!("Waiting {}s for Helix rate limit reset", wait);
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+        }
+    }
+
+    fn update_rate_limit(&self, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+        };
+
+        let mut rate_limit = self.rate_limit.lock().unwrap();
+        rate_limit.remaining = header("ratelimit-remaining");
+        rate_limit.reset = header("ratelimit-reset");
+    }
+
+    async fn get_data<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        kind: TokenKind,
+    ) -> Result<Vec<T>> {
+        let response = self
+            .send(Method::GET, &self.url(path), kind, |request| {
+                request.query(query)
+            })
+            .await?;
+        let response: DataResponse<T> = response.json().await?;
+        Ok(response.data)
+    }
+
+    async fn get_paginated<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        kind: TokenKind,
+    ) -> Result<Vec<T>> {
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = self
+                .send(Method::GET, &self.url(path), kind, |request| {
+                    let request = request.query(query).query(&[("first", PAGE_SIZE)]);
+                    match &cursor {
+                        Some(cursor) => request.query(&[("after", cursor)]),
+                        None => request,
+                    }
+                })
+                .await?;
+
+            let page: DataResponse<T> = response.json().await?;
+            let done = page.data.is_empty();
+            results.extend(page.data);
+
+            cursor = page.pagination.and_then(|p| p.cursor);
+            if done || cursor.is_none() {
+                return Ok(results);
+            }
+        }
+    }
+
+    async fn send_json(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> Result<Response> {
+        self.send(method, &self.url(path), TokenKind::User, |request| {
+            let request = request.query(query);
+            match &body {
+                Some(body) => request.json(body),
+                None => request,
+            }
+        })
+        .await
+    }
+
+    pub async fn broadcaster_id(&self) -> Result<String> {
+        self.broadcaster_id
+            .get_or_try_init(|| async {
+                self.get_user(&self.config.twitch.channel)
+                    .await?
+                    .map(|user| user.id)
+                    .ok_or_else(|| BotError::Config("broadcaster not found".to_string()))
+            })
+            .await
+            .cloned()
+    }
+
+    async fn moderator_id(&self) -> Result<String> {
+        match self.tokens.info() {
+            Some(info) => Ok(info.user_id),
+            None => Ok(self.tokens.validate().await?.user_id),
+        }
+    }
+
+    pub async fn create_eventsub_subscription(
+        &self,
+        kind: &str,
+        condition: Value,
+        session_id: &str,
+    ) -> Result<()> {
+        let url = self
+            .config
+            .eventsub
+            .subscriptions_url
+            .clone()
+            .unwrap_or_else(|| self.url("eventsub/subscriptions"));
+
+        let body = json!({
+            "type": kind,
+            "version": "1",
+            "condition": condition,
+            "transport": {
+                "method": "websocket",
+                "session_id": session_id,
+            },
+        });
+
+        self.send(Method::POST, &url, TokenKind::User, |request| {
+            request.json(&body)
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
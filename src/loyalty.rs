@@ -0,0 +1,181 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, error, info};
+
+use crate::{
+    bot::Membership, config::Config, error::Result, helix::HelixClient, storage::SharedStorage,
+};
+
+#[derive(Default)]
+struct Presence {
+    joined: HashSet<String>,
+    active: HashMap<String, Instant>,
+}
+
+#[derive(Clone)]
+pub struct Loyalty {
+    config: Arc<Config>,
+    helix: HelixClient,
+    storage: SharedStorage,
+    presence: Arc<Mutex<Presence>>,
+    live: Arc<AtomicBool>,
+}
+
+impl Loyalty {
+    pub fn new(config: Arc<Config>, helix: HelixClient, storage: SharedStorage) -> Self {
+        Self {
+            config,
+            helix,
+            storage,
+            presence: Arc::new(Mutex::new(Presence::default())),
+            live: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn record_membership(&self, membership: &Membership) {
+        let mut presence = self.presence.lock().unwrap();
+        match membership {
+            Membership::Join(login) => presence.joined.insert(login.clone()),
+            Membership::Part(login) => presence.joined.remove(login),
+        };
+    }
+
+    pub fn record_chat(&self, login: &str) {
+        self.presence
+            .lock()
+            .unwrap()
+            .active
+            .insert(login.to_lowercase(), Instant::now());
+    }
+
+    pub fn set_live(&self, live: bool) {
+        self.live.store(live, Ordering::Relaxed);
+    }
+
+    pub fn points(&self, login: &str) -> Result<i64> {
+        self.storage.points(login)
+    }
+
+    pub fn watch_time(&self, login: &str) -> Result<u64> {
+        self.storage.watch_time(login)
+    }
+
+    pub fn give(&self, login: &str, amount: i64) -> Result<i64> {
+        self.storage.add_points(login, amount)
+    }
+
+    pub fn spend(&self, login: &str, cost: i64) -> Result<bool> {
+        self.storage.spend_points(login, cost)
+    }
+
+    pub fn top_points(&self, limit: usize) -> Result<Vec<(String, i64)>> {
+        self.storage.top_points(limit)
+    }
+
+    pub fn top_watch_time(&self, limit: usize) -> Result<Vec<(String, u64)>> {
+        self.storage.top_watch_time(limit)
+    }
+
+    pub fn spawn(&self) {
+        if !self.config.loyalty.enabled {
+            return;
+        }
+
+        let loyalty = self.clone();
+        tokio::spawn(async move {
+            let period = Duration::from_secs(loyalty.config.loyalty.interval.max(1) * 60);
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                loyalty.refresh_live().await;
+                let chatters = loyalty.chatters().await;
+                if let Err(e) = loyalty.accrue(period, &chatters) {
+                    error!("Failed to accrue loyalty points: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn refresh_live(&self) {
+        if !self.helix.is_configured() {
+            return;
+        }
+
+        let live = match self.helix.broadcaster_id().await {
+            Ok(id) => self
+                .helix
+                .get_stream(&id)
+                .await
+                .map(|stream| stream.is_some()),
+            Err(e) => Err(e),
+        };
+
+        match live {
+            Ok(live) => self.set_live(live),
+            Err(e) => debug!("Couldn't check stream status: {}", e),
+        }
+    }
+
+    async fn chatters(&self) -> Vec<String> {
+        if !self.helix.is_configured() || !self.live.load(Ordering::Relaxed) {
+            return Vec::new();
+        }
+
+        match self.helix.get_chatters().await {
+            Ok(chatters) => chatters
+                .into_iter()
+                .map(|chatter| chatter.user_login.to_lowercase())
+                .collect(),
+            Err(e) => {
+                debug!("Couldn't list chatters, using chat presence only: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn accrue(&self, period: Duration, chatters: &[String]) -> Result<()> {
+        if !self.live.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let config = &self.config.loyalty;
+        let window = Duration::from_secs(config.active_window * 60);
+        let ignored: HashSet<String> = config
+            .ignored
+            .iter()
+            .map(|login| login.to_lowercase())
+            .chain([self.config.twitch.username.to_lowercase()])
+            .collect();
+
+        let viewers: Vec<(String, bool)> = {
+            let mut presence = self.presence.lock().unwrap();
+            presence.active.retain(|_, seen| seen.elapsed() < window);
+
+            let mut viewers: HashSet<&String> = presence.joined.iter().collect();
+            viewers.extend(presence.active.keys());
+            viewers.extend(chatters);
+            viewers
+                .into_iter()
+                .filter(|login| !ignored.contains(*login))
+                .map(|login| (login.clone(), presence.active.contains_key(login)))
+                .collect()
+        };
+
+        for (login, active) in &viewers {
+            let bonus = if *active { config.active_bonus } else { 0 };
+            self.storage
+                .accrue(login, config.points_per_interval + bonus, period.as_secs())?;
+        }
+
+        info!("Accrued loyalty for {} viewers", viewers.len());
+        Ok(())
+    }
+}
@@ -1,7 +1,18 @@
 mod bot;
 mod commands;
 mod config;
+mod counters;
 mod error;
+mod eventsub;
+mod giveaway;
+mod helix;
+mod loyalty;
+mod moderation;
+mod polls;
+mod quotes;
+mod storage;
+mod template;
+mod timers;
 
 use bot::TwitchBot;
 use config::Config;
@@ -18,7 +29,7 @@ async fn main() -> anyhow::Result<()> {
     info!("Starting Twitch bot");
 
     let config = Config::load()?;
-    let mut bot = TwitchBot::new(config);
+    let mut bot = TwitchBot::new(config)?;
 
     bot.connect().await?;
 
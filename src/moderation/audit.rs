@@ -0,0 +1,94 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub moderator: String,
+    pub action: String,
+    pub target: String,
+    pub duration: Option<u32>,
+    pub reason: String,
+}
+
+impl AuditEntry {
+    pub fn new(
+        moderator: &str,
+        action: &str,
+        target: &str,
+        duration: Option<u32>,
+        reason: &str,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            moderator: moderator.to_string(),
+            action: action.to_string(),
+            target: target.to_string(),
+            duration,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub async fn append(&self, entry: &AuditEntry) -> std::io::Result<()> {
+        let _guard = self.lock.lock().await;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await
+    }
+
+    pub async fn recent(&self, target: &str, limit: usize) -> std::io::Result<Vec<AuditEntry>> {
+        let _guard = self.lock.lock().await;
+
+        let contents = match fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let target = target.to_lowercase();
+        let mut entries: Vec<AuditEntry> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter(|entry| entry.target.to_lowercase() == target)
+            .collect();
+
+        let skip = entries.len().saturating_sub(limit);
+        Ok(entries.split_off(skip))
+    }
+}
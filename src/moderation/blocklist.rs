@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+use regex::Regex;
+use tracing::{error, info, warn};
+
+use super::normalize::{contains_term, normalize, normalize_plain};
+use crate::config::BlocklistConfig;
+
+const REGEX_PREFIX: &str = "re:";
+
+enum Term {
+    Word { raw: String, normalized: String },
+    Pattern { raw: String, regex: Regex },
+}
+
+impl Term {
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if raw.is_empty() || raw.starts_with('#') {
+            return None;
+        }
+
+        match raw.strip_prefix(REGEX_PREFIX) {
+            Some(pattern) => match Regex::new(&format!("(?i){}", pattern)) {
+                Ok(regex) => Some(Term::Pattern {
+                    raw: raw.to_string(),
+                    regex,
+                }),
+                Err(e) => {
+                    warn!("Invalid blocklist regex {}: {}", pattern, e);
+                    None
+                }
+            },
+            None => Some(Term::Word {
+                raw: raw.to_lowercase(),
+                normalized: normalize(raw),
+            }),
+        }
+    }
+
+    fn raw(&self) -> String {
+        match self {
+            Term::Word { raw, .. } | Term::Pattern { raw, .. } => raw.clone(),
+        }
+    }
+
+    fn matches(&self, text: &str, forms: &[String]) -> bool {
+        match self {
+            Term::Word {
+                normalized: term, ..
+            } => forms.iter().any(|form| contains_term(form, term)),
+            Term::Pattern { regex, .. } => {
+                regex.is_match(text) || forms.iter().any(|form| regex.is_match(form))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Terms {
+    config: Vec<Term>,
+    runtime: Vec<Term>,
+    modified: Option<SystemTime>,
+}
+
+pub enum BlockEdit {
+    Added,
+    Removed,
+    Exists,
+    Missing,
+    Protected,
+    Invalid,
+}
+
+#[derive(Clone)]
+pub struct Blocklist {
+    enabled: bool,
+    path: PathBuf,
+    terms: Arc<RwLock<Terms>>,
+}
+
+impl Blocklist {
+    pub fn new(config: &BlocklistConfig) -> Self {
+        let terms = Terms {
+            config: config
+                .words
+                .iter()
+                .filter_map(|word| Term::parse(word))
+                .chain(
+                    config
+                        .regexes
+                        .iter()
+                        .filter_map(|pattern| Term::parse(&format!("{}{}", REGEX_PREFIX, pattern))),
+                )
+                .collect(),
+            ..Terms::default()
+        };
+
+        let blocklist = Self {
+            enabled: config.enabled,
+            path: PathBuf::from(&config.terms_file),
+            terms: Arc::new(RwLock::new(terms)),
+        };
+        blocklist.reload_if_changed();
+        blocklist
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+
+    fn reload_if_changed(&self) {
+        let modified = self.modified();
+        if modified == self.terms.read().unwrap().modified {
+            return;
+        }
+
+        let runtime: Vec<Term> = fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(Term::parse)
+            .collect();
+
+        info!(
+            "Loaded {} blocklist terms from {}",
+            runtime.len(),
+            self.path.display()
+        );
+
+        let mut terms = self.terms.write().unwrap();
+        terms.runtime = runtime;
+        terms.modified = modified;
+    }
+
+    fn save(&self, terms: &mut Terms) {
+        let contents: String = terms
+            .runtime
+            .iter()
+            .map(|term| format!("{}\n", term.raw()))
+            .collect();
+
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+
+        match fs::write(&self.path, contents) {
+            Ok(()) => terms.modified = self.modified(),
+            Err(e) => error!("Failed to save blocklist: {}", e),
+        }
+    }
+
+    pub fn matches(&self, text: &str) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.reload_if_changed();
+
+        let forms = [normalize(text), normalize_plain(text)];
+        let terms = self.terms.read().unwrap();
+        terms
+            .config
+            .iter()
+            .chain(&terms.runtime)
+            .find(|term| term.matches(text, &forms))
+            .map(Term::raw)
+    }
+
+    pub fn add(&self, raw: &str) -> BlockEdit {
+        self.reload_if_changed();
+
+        let Some(term) = Term::parse(raw) else {
+            return BlockEdit::Invalid;
+        };
+
+        let mut terms = self.terms.write().unwrap();
+        let raw = term.raw();
+        if terms
+            .config
+            .iter()
+            .chain(&terms.runtime)
+            .any(|t| t.raw() == raw)
+        {
+            return BlockEdit::Exists;
+        }
+
+        terms.runtime.push(term);
+        self.save(&mut terms);
+        BlockEdit::Added
+    }
+
+    pub fn remove(&self, raw: &str) -> BlockEdit {
+        self.reload_if_changed();
+
+        let raw = raw.trim().to_lowercase();
+        let mut terms = self.terms.write().unwrap();
+
+        if terms.config.iter().any(|t| t.raw().to_lowercase() == raw) {
+            return BlockEdit::Protected;
+        }
+
+        let before = terms.runtime.len();
+        terms.runtime.retain(|t| t.raw().to_lowercase() != raw);
+        if terms.runtime.len() == before {
+            return BlockEdit::Missing;
+        }
+
+        self.save(&mut terms);
+        BlockEdit::Removed
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.reload_if_changed();
+
+        let terms = self.terms.read().unwrap();
+        terms
+            .config
+            .iter()
+            .chain(&terms.runtime)
+            .map(Term::raw)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocklist(words: &[&str]) -> Blocklist {
+        Blocklist::new(&BlocklistConfig {
+            enabled: true,
+            terms_file: "/nonexistent/blocklist.txt".to_string(),
+            words: words.iter().map(|word| word.to_string()).collect(),
+            regexes: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn matches_terms_next_to_punctuation() {
+        let blocklist = blocklist(&["spam"]);
+        assert_eq!(blocklist.matches("spam!"), Some("spam".to_string()));
+        assert_eq!(blocklist.matches("(spam"), Some("spam".to_string()));
+        assert_eq!(
+            blocklist.matches("no (spam) here"),
+            Some("spam".to_string())
+        );
+    }
+
+    #[test]
+    fn still_matches_symbol_substitutions() {
+        let blocklist = blocklist(&["ass"]);
+        assert_eq!(blocklist.matches("what an @ss"), Some("ass".to_string()));
+        assert_eq!(blocklist.matches("classic pass"), None);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{error, info};
+
+use super::{
+    blocklist::Blocklist,
+    rules,
+    service::{Action, ModerationService},
+};
+use crate::{
+    bot::TwitchMessage,
+    config::{Config, FilterAction},
+};
+
+pub enum Verdict {
+    Allow,
+    Warn(String),
+    Block(Option<String>),
+}
+
+struct Violation {
+    rule: &'static str,
+    action: FilterAction,
+    warning: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct HeldMessage {
+    pub user_id: String,
+    pub login: String,
+    pub text: String,
+}
+
+#[derive(Default)]
+struct FilterState {
+    history: HashMap<String, VecDeque<(String, Instant)>>,
+    offenses: HashMap<String, (usize, Instant)>,
+    permits: HashMap<String, Instant>,
+    held: HashMap<String, HeldMessage>,
+}
+
+#[derive(Clone)]
+pub struct ChatFilter {
+    config: Arc<Config>,
+    service: ModerationService,
+    blocklist: Blocklist,
+    state: Arc<Mutex<FilterState>>,
+}
+
+impl ChatFilter {
+    pub fn new(config: Arc<Config>, service: ModerationService, blocklist: Blocklist) -> Self {
+        Self {
+            config,
+            service,
+            blocklist,
+            state: Arc::new(Mutex::new(FilterState::default())),
+        }
+    }
+
+    pub fn permit(&self, login: &str) -> u64 {
+        let seconds = self.config.moderation.permit_seconds;
+        let expires = Instant::now() + Duration::from_secs(seconds);
+        self.state
+            .lock()
+            .unwrap()
+            .permits
+            .insert(login.to_lowercase(), expires);
+        seconds
+    }
+
+    pub fn take_held(&self, login: &str) -> Option<HeldMessage> {
+        self.state
+            .lock()
+            .unwrap()
+            .held
+            .remove(&login.to_lowercase())
+    }
+
+    pub async fn check(&self, message: &TwitchMessage) -> Verdict {
+        if message.has_permissions() {
+            return Verdict::Allow;
+        }
+
+        if let Some(term) = self.blocklist.matches(&message.message) {
+            info!("{} matched blocklist term {}", message.username, term);
+            let reason = format!("blocklist: {}", term);
+            if let Err(e) = self
+                .service
+                .delete_message(
+                    &self.config.twitch.username,
+                    &message.message_id,
+                    &message.username,
+                    &reason,
+                )
+                .await
+            {
+                error!("Failed to delete blocked message: {}", e);
+            }
+            return Verdict::Block(None);
+        }
+
+        if self.config.moderation.hold_first_links
+            && message.is_first_message
+            && !self.is_spotify_reward(message)
+            && !rules::link_hosts(&message.message).is_empty()
+        {
+            return self.hold(message).await;
+        }
+
+        match self.find_violation(message) {
+            Some(violation) => self.enforce(message, violation).await,
+            None => Verdict::Allow,
+        }
+    }
+
+    fn find_violation(&self, message: &TwitchMessage) -> Option<Violation> {
+        let config = &self.config.moderation;
+        let login = message.username.to_lowercase();
+        let text = message.message.as_str();
+        let mut state = self.state.lock().unwrap();
+
+        if config.repeats.enabled {
+            let window = Duration::from_secs(config.repeats.window);
+            let now = Instant::now();
+            let normalized = text.trim().to_lowercase();
+
+            let history = state.history.entry(login.clone()).or_default();
+            history.retain(|(_, sent)| now - *sent < window);
+            history.push_back((normalized.clone(), now));
+
+            let repeats = history.iter().filter(|(m, _)| *m == normalized).count();
+            if repeats >= config.repeats.max_repeats {
+                return Some(Violation {
+                    rule: "repeats",
+                    action: config.repeats.action,
+                    warning: "stop repeating yourself",
+                });
+            }
+        }
+
+        if config.length.enabled && text.chars().count() > config.length.max_length {
+            return Some(Violation {
+                rule: "length",
+                action: config.length.action,
+                warning: "that message is way too long",
+            });
+        }
+
+        if config.links.enabled && !self.is_spotify_reward(message) {
+            let has_blocked_link = rules::link_hosts(text)
+                .iter()
+                .any(|host| !rules::is_allowed_domain(host, &config.links.allowed_domains));
+
+            if has_blocked_link {
+                let permitted = state
+                    .permits
+                    .remove(&login)
+                    .is_some_and(|expires| expires > Instant::now());
+
+                if !permitted {
+                    return Some(Violation {
+                        rule: "links",
+                        action: config.links.action,
+                        warning: "no links without a !permit",
+                    });
+                }
+            }
+        }
+
+        if config.caps.enabled
+            && rules::caps_ratio(text, config.caps.min_letters)
+                .is_some_and(|ratio| ratio > config.caps.max_ratio)
+        {
+            return Some(Violation {
+                rule: "caps",
+                action: config.caps.action,
+                warning: "chill with the caps",
+            });
+        }
+
+        if config.symbols.enabled
+            && rules::symbol_ratio(text, config.symbols.min_length)
+                .is_some_and(|ratio| ratio > config.symbols.max_ratio)
+        {
+            return Some(Violation {
+                rule: "symbols",
+                action: config.symbols.action,
+                warning: "too many symbols",
+            });
+        }
+
+        if config.emotes.enabled && message.emote_count > config.emotes.max_count {
+            return Some(Violation {
+                rule: "emotes",
+                action: config.emotes.action,
+                warning: "too many emotes",
+            });
+        }
+
+        None
+    }
+
+    async fn hold(&self, message: &TwitchMessage) -> Verdict {
+        let moderator = &self.config.twitch.username;
+        let reason = "first message with a link held for review";
+        let login = message.username.to_lowercase();
+        info!("Holding first message from {} for review", login);
+
+        if let Err(e) = self
+            .service
+            .delete_message(moderator, &message.message_id, &login, reason)
+            .await
+        {
+            error!("Failed to delete held message: {}", e);
+        }
+
+        let timeout = Action::Timeout(self.config.moderation.first_link_timeout);
+        if let Err(e) = self
+            .service
+            .apply_to(moderator, &message.user_id, &login, timeout, reason)
+            .await
+        {
+            error!("Failed to time out held chatter: {}", e);
+        }
+
+        self.state.lock().unwrap().held.insert(
+            login.clone(),
+            HeldMessage {
+                user_id: message.user_id.clone(),
+                login: login.clone(),
+                text: message.message.clone(),
+            },
+        );
+
+        Verdict::Block(Some(format!(
+            "held a first message from {} with a link, mods can !approve {} or !deny {}",
+            login, login, login
+        )))
+    }
+
+    fn is_spotify_reward(&self, message: &TwitchMessage) -> bool {
+        message.custom_reward_id.as_ref() == Some(&self.config.spotify.reward_id)
+    }
+
+    fn escalate(&self, login: &str) -> u32 {
+        let config = &self.config.moderation;
+        let reset = Duration::from_secs(config.escalation_reset);
+        let now = Instant::now();
+
+        let mut state = self.state.lock().unwrap();
+        let offense = state
+            .offenses
+            .entry(login.to_lowercase())
+            .or_insert((0, now));
+
+        if now - offense.1 > reset {
+            offense.0 = 0;
+        }
+        offense.0 += 1;
+        offense.1 = now;
+
+        let index = (offense.0 - 1).min(config.escalation.len().saturating_sub(1));
+        config
+            .escalation
+            .get(index)
+            .copied()
+            .unwrap_or(config.default_timeout)
+    }
+
+    async fn enforce(&self, message: &TwitchMessage, violation: Violation) -> Verdict {
+        let moderator = &self.config.twitch.username;
+        let reason = format!("automod: {}", violation.rule);
+        info!("{} tripped the {} filter", message.username, violation.rule);
+
+        let (result, suffix) = match violation.action {
+            FilterAction::Delete => (
+                self.service
+                    .delete_message(moderator, &message.message_id, &message.username, &reason)
+                    .await,
+                String::new(),
+            ),
+            FilterAction::Timeout => {
+                let duration = self.escalate(&message.username);
+                (
+                    self.service
+                        .apply_to(
+                            moderator,
+                            &message.user_id,
+                            &message.username,
+                            Action::Timeout(duration),
+                            &reason,
+                        )
+                        .await,
+                    format!(" ({}s timeout)", duration),
+                )
+            }
+            FilterAction::Warn => (Ok(()), " (warning)".to_string()),
+        };
+
+        if let Err(e) = result {
+            error!("Failed to enforce {} filter: {}", violation.rule, e);
+        }
+
+        let response = format!("@{} {}{}", message.username, violation.warning, suffix);
+        match violation.action {
+            FilterAction::Warn => Verdict::Warn(response),
+            _ => Verdict::Block(Some(response)),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub mod audit;
+mod blocklist;
+mod filter;
+mod normalize;
+mod rules;
+mod service;
+
+pub use blocklist::{BlockEdit, Blocklist};
+pub use filter::{ChatFilter, Verdict};
+pub use service::{Action, ModerationService, NO_REASON};
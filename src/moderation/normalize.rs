@@ -0,0 +1,166 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+fn fold(c: char) -> char {
+    match c {
+        'а' | 'ɑ' | 'α' | '@' | '4' => 'a',
+        'в' | 'β' | '8' => 'b',
+        'с' | 'ϲ' | '¢' | '(' => 'c',
+        'е' | 'ε' | '€' | '3' => 'e',
+        'һ' => 'h',
+        'і' | 'ı' | 'ι' | '!' | '1' | '|' => 'i',
+        'ј' => 'j',
+        'к' | 'κ' => 'k',
+        'м' => 'm',
+        'п' | 'η' => 'n',
+        'о' | 'ο' | 'σ' | '0' => 'o',
+        'р' | 'ρ' => 'p',
+        'ѕ' | '$' | '5' => 's',
+        'т' | 'τ' | '+' | '7' => 't',
+        'υ' | 'μ' => 'u',
+        'ν' => 'v',
+        'ш' | 'ω' => 'w',
+        'х' | 'χ' => 'x',
+        'у' | 'γ' => 'y',
+        'ᴢ' | '2' => 'z',
+        c => c,
+    }
+}
+
+pub fn normalize(text: &str) -> String {
+    normalize_with(text, true)
+}
+
+pub fn normalize_plain(text: &str) -> String {
+    normalize_with(text, false)
+}
+
+fn normalize_with(text: &str, fold_symbols: bool) -> String {
+    let folded: String = text
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .map(|c| {
+            if fold_symbols || c.is_alphanumeric() {
+                fold(c)
+            } else {
+                c
+            }
+        })
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+
+    let mut words: Vec<String> = Vec::new();
+    let mut spaced = String::new();
+
+    for word in folded.split_whitespace() {
+        if word.chars().count() == 1 {
+            spaced.push_str(word);
+            continue;
+        }
+        if !spaced.is_empty() {
+            words.push(std::mem::take(&mut spaced));
+        }
+        words.push(word.to_string());
+    }
+    if !spaced.is_empty() {
+        words.push(spaced);
+    }
+
+    words.join(" ")
+}
+
+fn dedup(word: &str) -> String {
+    let mut chars: Vec<char> = word.chars().collect();
+    chars.dedup();
+    chars.into_iter().collect()
+}
+
+fn word_matches(word: &str, term: &str) -> bool {
+    word == term || (word.chars().count() >= term.chars().count() && dedup(word) == dedup(term))
+}
+
+pub fn contains_term(normalized_text: &str, normalized_term: &str) -> bool {
+    let words: Vec<&str> = normalized_text.split(' ').collect();
+    let terms: Vec<&str> = normalized_term
+        .split(' ')
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if terms.is_empty() || terms.len() > words.len() {
+        return false;
+    }
+
+    words.windows(terms.len()).any(|window| {
+        window
+            .iter()
+            .zip(&terms)
+            .all(|(word, term)| word_matches(word, term))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowercases_and_collapses_whitespace() {
+        assert_eq!(normalize("  Hello   WORLD "), "hello world");
+    }
+
+    #[test]
+    fn folds_leetspeak() {
+        assert_eq!(normalize("h4x0r"), normalize("haxor"));
+        assert_eq!(normalize("$p4m"), "spam");
+    }
+
+    #[test]
+    fn folds_confusables_and_accents() {
+        assert_eq!(normalize("ѕрам"), "spam");
+        assert_eq!(normalize("spåm"), "spam");
+        assert_eq!(normalize("ｓｐａｍ"), "spam");
+    }
+
+    #[test]
+    fn matches_stretched_words() {
+        let term = normalize("spam");
+        assert!(contains_term(&normalize("spaaaaam"), &term));
+        assert!(contains_term(&normalize("SPAMMM"), &term));
+    }
+
+    #[test]
+    fn does_not_match_shorter_words_with_same_letters() {
+        assert!(!contains_term(&normalize("as if"), &normalize("ass")));
+    }
+
+    #[test]
+    fn joins_spaced_out_letters() {
+        assert_eq!(normalize("s p a m"), "spam");
+        assert_eq!(normalize("s.p.a.m now"), "spam now");
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        let term = normalize("ass");
+        assert!(contains_term(&normalize("what an @ss"), &term));
+        assert!(!contains_term(&normalize("classic pass"), &term));
+    }
+
+    #[test]
+    fn matches_multi_word_terms() {
+        let term = normalize("bad word");
+        assert!(contains_term(&normalize("such a B4D W0RD"), &term));
+        assert!(!contains_term(&normalize("bad sword"), &term));
+    }
+
+    #[test]
+    fn plain_form_treats_punctuation_as_separators() {
+        assert_eq!(normalize_plain("spam!"), "spam");
+        assert_eq!(normalize_plain("(spam)"), "spam");
+        assert_eq!(normalize_plain("h4x0r"), normalize("haxor"));
+    }
+
+    #[test]
+    fn empty_term_never_matches() {
+        assert!(!contains_term(&normalize("anything"), ""));
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(https?://)?((?:[a-z0-9-]+\.)+([a-z]{2,24}))(?::\d+)?(?:/\S*)?").unwrap()
+});
+
+const BARE_TLDS: &[&str] = &[
+    "com", "net", "org", "io", "gg", "tv", "me", "co", "info", "biz", "xyz", "ru", "de", "uk",
+    "ly", "app", "dev", "link", "live", "shop", "site", "online", "store", "club", "top", "fun",
+    "click", "win", "us", "eu", "cc", "to", "su", "tk",
+];
+
+pub fn link_hosts(text: &str) -> Vec<String> {
+    LINK.captures_iter(text)
+        .filter_map(|captures| {
+            let host = captures[2].to_lowercase();
+            let tld = captures[3].to_lowercase();
+            let explicit = captures.get(1).is_some() || host.starts_with("www.");
+            (explicit || BARE_TLDS.contains(&tld.as_str())).then_some(host)
+        })
+        .collect()
+}
+
+pub fn is_allowed_domain(host: &str, allowed: &[String]) -> bool {
+    allowed.iter().any(|domain| {
+        let domain = domain.to_lowercase();
+        host == domain || host.ends_with(&format!(".{}", domain))
+    })
+}
+
+pub fn caps_ratio(text: &str, min_letters: usize) -> Option<f64> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() < min_letters {
+        return None;
+    }
+
+    let upper = letters.iter().filter(|c| c.is_uppercase()).count();
+    Some(upper as f64 / letters.len() as f64)
+}
+
+pub fn symbol_ratio(text: &str, min_length: usize) -> Option<f64> {
+    let visible: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if visible.len() < min_length {
+        return None;
+    }
+
+    let symbols = visible.iter().filter(|c| !c.is_alphanumeric()).count();
+    Some(symbols as f64 / visible.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_links_with_schemes_or_known_tlds() {
+        assert_eq!(
+            link_hosts("go to https://Evil.example/path or www.spam.shop"),
+            vec!["evil.example", "www.spam.shop"]
+        );
+        assert_eq!(
+            link_hosts("free followers at bigfollows.com"),
+            vec!["bigfollows.com"]
+        );
+        assert_eq!(
+            link_hosts("see http://localhost.test:8080"),
+            vec!["localhost.test"]
+        );
+    }
+
+    #[test]
+    fn ignores_dotted_words() {
+        assert!(link_hosts("check main.rs").is_empty());
+        assert!(link_hosts("ok.thanks gg.wp").is_empty());
+        assert!(link_hosts("no links here").is_empty());
+    }
+
+    #[test]
+    fn allows_domains_and_subdomains() {
+        let allowed = vec!["Twitch.tv".to_string()];
+        assert!(is_allowed_domain("twitch.tv", &allowed));
+        assert!(is_allowed_domain("clips.twitch.tv", &allowed));
+        assert!(!is_allowed_domain("nottwitch.tv", &allowed));
+    }
+
+    #[test]
+    fn measures_caps_and_symbols() {
+        assert_eq!(caps_ratio("HELLO there", 5), Some(0.5));
+        assert_eq!(caps_ratio("HI", 5), None);
+        assert_eq!(symbol_ratio("a!b! ", 4), Some(0.5));
+        assert_eq!(symbol_ratio("!!", 4), None);
+    }
+}
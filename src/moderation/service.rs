@@ -0,0 +1,114 @@
+use tracing::error;
+
+use super::audit::{AuditEntry, AuditLog};
+use crate::{error::Result, helix::HelixClient};
+
+pub const NO_REASON: &str = "no reason given";
+const MODLOG_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Timeout(u32),
+    Ban,
+    Unban,
+}
+
+impl Action {
+    fn name(&self) -> &'static str {
+        match self {
+            Action::Timeout(_) => "timeout",
+            Action::Ban => "ban",
+            Action::Unban => "unban",
+        }
+    }
+
+    fn duration(&self) -> Option<u32> {
+        match self {
+            Action::Timeout(seconds) => Some(*seconds),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ModerationService {
+    helix: HelixClient,
+    audit: AuditLog,
+}
+
+impl ModerationService {
+    pub fn new(helix: HelixClient, audit: AuditLog) -> Self {
+        Self { helix, audit }
+    }
+
+    pub async fn apply(
+        &self,
+        moderator: &str,
+        login: &str,
+        action: Action,
+        reason: &str,
+    ) -> Result<bool> {
+        let Some(user) = self.helix.get_user(login).await? else {
+            return Ok(false);
+        };
+
+        self.apply_to(moderator, &user.id, &user.login, action, reason)
+            .await?;
+        Ok(true)
+    }
+
+    pub async fn apply_to(
+        &self,
+        moderator: &str,
+        user_id: &str,
+        login: &str,
+        action: Action,
+        reason: &str,
+    ) -> Result<()> {
+        match action {
+            Action::Timeout(seconds) => self.helix.ban_user(user_id, Some(seconds), reason).await?,
+            Action::Ban => self.helix.ban_user(user_id, None, reason).await?,
+            Action::Unban => self.helix.unban_user(user_id).await?,
+        }
+
+        self.log(moderator, action.name(), login, action.duration(), reason)
+            .await;
+        Ok(())
+    }
+
+    pub async fn delete_message(
+        &self,
+        moderator: &str,
+        message_id: &str,
+        login: &str,
+        reason: &str,
+    ) -> Result<()> {
+        self.helix.delete_chat_messages(Some(message_id)).await?;
+        self.log(moderator, "delete", login, None, reason).await;
+        Ok(())
+    }
+
+    pub async fn clear(&self, moderator: &str) -> Result<()> {
+        self.helix.delete_chat_messages(None).await?;
+        self.log(moderator, "clear", "chat", None, NO_REASON).await;
+        Ok(())
+    }
+
+    async fn log(
+        &self,
+        moderator: &str,
+        action: &str,
+        target: &str,
+        duration: Option<u32>,
+        reason: &str,
+    ) {
+        let entry = AuditEntry::new(moderator, action, target, duration, reason);
+        if let Err(e) = self.audit.append(&entry).await {
+            error!("Failed to write audit log: {}", e);
+        }
+    }
+
+    pub async fn history(&self, login: &str) -> Result<Vec<AuditEntry>> {
+        Ok(self.audit.recent(login, MODLOG_LIMIT).await?)
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::{
+    config::Config,
+    error::Result,
+    helix::{HelixClient, types::Poll},
+};
+
+const NATIVE_TITLE_LIMIT: usize = 60;
+const NATIVE_CHOICE_LIMIT: usize = 25;
+const NATIVE_MAX_CHOICES: usize = 5;
+const NATIVE_DURATION: std::ops::RangeInclusive<u64> = 15..=1800;
+const NATIVE_GRACE: u64 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollSpec {
+    pub question: String,
+    pub options: Vec<String>,
+}
+
+pub fn parse_poll(text: &str) -> Option<PollSpec> {
+    let text = text.trim();
+    let (question, rest) = match text.strip_prefix('"') {
+        Some(quoted) => {
+            let (question, rest) = quoted.split_once('"')?;
+            (question.trim(), rest)
+        }
+        None => text.split_once('?').map(|(q, rest)| (q.trim(), rest))?,
+    };
+
+    let options: Vec<String> = rest
+        .split('|')
+        .map(str::trim)
+        .filter(|option| !option.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if question.is_empty() || options.len() < 2 {
+        return None;
+    }
+
+    let question = if text.starts_with('"') {
+        question.to_string()
+    } else {
+        format!("{}?", question)
+    };
+
+    Some(PollSpec { question, options })
+}
+
+pub fn tally(options: &[String], votes: impl IntoIterator<Item = usize>) -> Vec<u64> {
+    let mut counts = vec![0; options.len()];
+    for choice in votes {
+        if let Some(count) = counts.get_mut(choice) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+pub fn describe_result(spec: &PollSpec, counts: &[u64]) -> String {
+    let total: u64 = counts.iter().sum();
+    if total == 0 {
+        return format!("poll over: {} nobody voted", spec.question);
+    }
+
+    let best = counts.iter().copied().max().unwrap_or(0);
+    let winners: Vec<&str> = spec
+        .options
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| **count == best)
+        .map(|(option, _)| option.as_str())
+        .collect();
+
+    let percent = best * 100 / total;
+    match winners.as_slice() {
+        [winner] => format!(
+            "poll over: {} {} wins with {} of {} votes ({}%)",
+            spec.question, winner, best, total, percent
+        ),
+        tied => format!(
+            "poll over: {} tie between {} with {} votes each",
+            spec.question,
+            tied.join(" and "),
+            best
+        ),
+    }
+}
+
+pub fn describe_tally(spec: &PollSpec, counts: &[u64]) -> String {
+    let options = spec
+        .options
+        .iter()
+        .zip(counts)
+        .enumerate()
+        .map(|(index, (option, count))| format!("{}. {} ({})", index + 1, option, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{} {}", spec.question, options)
+}
+
+fn native_duration(duration: Duration) -> u64 {
+    (duration.as_secs() + NATIVE_GRACE).min(*NATIVE_DURATION.end())
+}
+
+pub enum Vote {
+    Counted,
+    AlreadyVoted,
+    Invalid(usize),
+    NoPoll,
+}
+
+struct ActivePoll {
+    id: u64,
+    spec: PollSpec,
+    votes: HashMap<String, usize>,
+    ends_at: Instant,
+    native_id: Option<String>,
+}
+
+impl ActivePoll {
+    fn counts(&self) -> Vec<u64> {
+        tally(&self.spec.options, self.votes.values().copied())
+    }
+}
+
+#[derive(Default)]
+struct PollState {
+    active: Option<ActivePoll>,
+    starting: bool,
+    next_id: u64,
+}
+
+struct Reservation<'a> {
+    state: &'a Mutex<PollState>,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.state.lock().unwrap().starting = false;
+    }
+}
+
+#[derive(Clone)]
+pub struct Polls {
+    config: Arc<Config>,
+    helix: HelixClient,
+    state: Arc<Mutex<PollState>>,
+    outbox: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>,
+}
+
+impl Polls {
+    pub fn new(config: Arc<Config>, helix: HelixClient) -> Self {
+        Self {
+            config,
+            helix,
+            state: Arc::new(Mutex::new(PollState::default())),
+            outbox: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn attach(&self, outbox: mpsc::UnboundedSender<String>) {
+        *self.outbox.lock().unwrap() = Some(outbox);
+    }
+
+    fn reserve(&self) -> Option<Reservation<'_>> {
+        let mut state = self.state.lock().unwrap();
+        if state.active.is_some() || state.starting {
+            return None;
+        }
+        state.starting = true;
+        Some(Reservation { state: &self.state })
+    }
+
+    pub async fn start(&self, spec: PollSpec, duration: Option<Duration>) -> String {
+        let Some(reservation) = self.reserve() else {
+            return "a poll is already running, end it with !poll end".to_string();
+        };
+
+        let duration = duration.unwrap_or(Duration::from_secs(self.config.polls.duration));
+        let ends_at = Instant::now() + duration;
+        let native_id = self.create_native(&spec, duration).await;
+
+        let id = {
+            let mut state = self.state.lock().unwrap();
+            state.next_id += 1;
+            let id = state.next_id;
+            state.active = Some(ActivePoll {
+                id,
+                spec: spec.clone(),
+                votes: HashMap::new(),
+                ends_at,
+                native_id,
+            });
+            id
+        };
+        drop(reservation);
+
+        self.spawn_ticker(id);
+
+        let options = spec
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| format!("{}. {}", index + 1, option))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "poll: {} {} (type the number or !vote <number>, {}s to vote)",
+            spec.question,
+            options,
+            duration.as_secs()
+        )
+    }
+
+    async fn create_native(&self, spec: &PollSpec, duration: Duration) -> Option<String> {
+        if !self.config.polls.mirror_native || !self.helix.is_configured() {
+            return None;
+        }
+
+        let fits = spec.question.chars().count() <= NATIVE_TITLE_LIMIT
+            && spec.options.len() <= NATIVE_MAX_CHOICES
+            && spec
+                .options
+                .iter()
+                .all(|option| option.chars().count() <= NATIVE_CHOICE_LIMIT)
+            && NATIVE_DURATION.contains(&duration.as_secs());
+        if !fits {
+            info!("Poll doesn't fit Twitch poll limits, running it in chat only");
+            return None;
+        }
+
+        match self
+            .helix
+            .create_poll(&spec.question, &spec.options, native_duration(duration))
+            .await
+        {
+            Ok(poll) => Some(poll.id),
+            Err(e) => {
+                warn!("Couldn't mirror poll to Twitch: {}", e);
+                None
+            }
+        }
+    }
+
+    fn spawn_ticker(&self, id: u64) {
+        let polls = self.clone();
+        let tally_every = Duration::from_secs(self.config.polls.tally_interval.max(5));
+
+        tokio::spawn(async move {
+            loop {
+                let remaining = {
+                    let state = polls.state.lock().unwrap();
+                    match &state.active {
+                        Some(active) if active.id == id => {
+                            active.ends_at.saturating_duration_since(Instant::now())
+                        }
+                        _ => return,
+                    }
+                };
+
+                if remaining.is_zero() {
+                    break;
+                }
+
+                tokio::time::sleep(remaining.min(tally_every)).await;
+                if remaining > tally_every
+                    && let Some(tally) = polls.current_tally(id)
+                {
+                    polls.send(tally);
+                }
+            }
+
+            if let Some(result) = polls.finish(Some(id)).await {
+                polls.send(result);
+            }
+        });
+    }
+
+    fn send(&self, message: String) {
+        if let Some(outbox) = self.outbox.lock().unwrap().as_ref() {
+            let _ = outbox.send(message);
+        }
+    }
+
+    fn current_tally(&self, id: u64) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        let active = state.active.as_ref().filter(|active| active.id == id)?;
+        Some(describe_tally(&active.spec, &active.counts()))
+    }
+
+    pub fn tally_message(&self) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        let active = state.active.as_ref()?;
+        Some(describe_tally(&active.spec, &active.counts()))
+    }
+
+    pub async fn end(&self) -> Option<String> {
+        self.finish(None).await
+    }
+
+    async fn finish(&self, id: Option<u64>) -> Option<String> {
+        let active = {
+            let mut state = self.state.lock().unwrap();
+            match &state.active {
+                Some(active) if id.is_none_or(|id| id == active.id) => state.active.take(),
+                _ => None,
+            }
+        }?;
+
+        let mut counts = active.counts();
+        if let Some(native_id) = &active.native_id {
+            match self.end_native(native_id).await {
+                Ok(poll) => {
+                    for (count, choice) in counts.iter_mut().zip(&poll.choices) {
+                        *count += choice.votes;
+                    }
+                }
+                Err(e) => error!("Failed to end Twitch poll: {}", e),
+            }
+        }
+
+        Some(describe_result(&active.spec, &counts))
+    }
+
+    async fn end_native(&self, native_id: &str) -> Result<Poll> {
+        match self.helix.end_poll(native_id).await {
+            Ok(poll) => Ok(poll),
+            Err(e) => {
+                warn!("Couldn't end Twitch poll, fetching its result: {}", e);
+                self.helix.get_poll(native_id).await
+            }
+        }
+    }
+
+    pub fn vote(&self, login: &str, choice: usize) -> Vote {
+        let mut state = self.state.lock().unwrap();
+        let Some(active) = state.active.as_mut() else {
+            return Vote::NoPoll;
+        };
+
+        if choice == 0 || choice > active.spec.options.len() {
+            return Vote::Invalid(active.spec.options.len());
+        }
+
+        let login = login.to_lowercase();
+        if active.votes.contains_key(&login) {
+            return Vote::AlreadyVoted;
+        }
+
+        active.votes.insert(login, choice - 1);
+        Vote::Counted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(options: &[&str]) -> PollSpec {
+        PollSpec {
+            question: "best map?".to_string(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_quoted_question_and_options() {
+        let spec = parse_poll(r#""best map?" ascent | bind | haven"#).unwrap();
+        assert_eq!(spec.question, "best map?");
+        assert_eq!(spec.options, vec!["ascent", "bind", "haven"]);
+    }
+
+    #[test]
+    fn parses_unquoted_question_ending_in_question_mark() {
+        let spec = parse_poll("pizza or tacos? pizza | tacos").unwrap();
+        assert_eq!(spec.question, "pizza or tacos?");
+        assert_eq!(spec.options, vec!["pizza", "tacos"]);
+    }
+
+    #[test]
+    fn rejects_polls_without_two_options() {
+        assert!(parse_poll(r#""question" only one"#).is_none());
+        assert!(parse_poll(r#""question" a | "#).is_none());
+        assert!(parse_poll(r#""" a | b"#).is_none());
+        assert!(parse_poll("no question mark a | b").is_none());
+    }
+
+    #[test]
+    fn native_polls_outlast_the_chat_poll() {
+        assert_eq!(native_duration(Duration::from_secs(60)), 65);
+        assert_eq!(native_duration(Duration::from_secs(1800)), 1800);
+    }
+
+    #[test]
+    fn tally_ignores_out_of_range_votes() {
+        let spec = spec(&["a", "b"]);
+        assert_eq!(tally(&spec.options, [0, 1, 1, 5]), vec![1, 2]);
+    }
+
+    #[test]
+    fn describes_winner_and_ties() {
+        let spec = spec(&["ascent", "bind", "haven"]);
+        assert_eq!(
+            describe_result(&spec, &[1, 3, 0]),
+            "poll over: best map? bind wins with 3 of 4 votes (75%)"
+        );
+        assert_eq!(
+            describe_result(&spec, &[2, 2, 0]),
+            "poll over: best map? tie between ascent and bind with 2 votes each"
+        );
+        assert_eq!(
+            describe_result(&spec, &[0, 0, 0]),
+            "poll over: best map? nobody voted"
+        );
+    }
+
+    #[test]
+    fn describes_running_tally() {
+        let spec = spec(&["a", "b"]);
+        assert_eq!(
+            describe_tally(&spec, &[2, 0]),
+            "best map? 1. a (2), 2. b (0)"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fs, path::PathBuf};
+
+use rand::seq::IndexedRandom;
+
+use crate::{
+    config::QuotesConfig,
+    error::Result,
+    storage::{NewQuote, Quote, SharedStorage},
+};
+
+#[derive(Default)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub collisions: Vec<u32>,
+}
+
+#[derive(Clone)]
+pub struct QuoteBook {
+    storage: SharedStorage,
+    export_path: PathBuf,
+}
+
+impl QuoteBook {
+    pub fn new(config: &QuotesConfig, storage: SharedStorage) -> Self {
+        Self {
+            storage,
+            export_path: PathBuf::from(&config.export_file),
+        }
+    }
+
+    pub fn export_path(&self) -> &std::path::Path {
+        &self.export_path
+    }
+
+    pub fn add(&self, quote: NewQuote) -> Result<Quote> {
+        self.storage.insert_quote(quote)
+    }
+
+    pub fn get(&self, id: u32) -> Result<Option<Quote>> {
+        self.storage.quote(id)
+    }
+
+    pub fn random(&self) -> Result<Option<Quote>> {
+        let quotes = self.storage.quotes()?;
+        Ok(quotes.choose(&mut rand::rng()).cloned())
+    }
+
+    pub fn search(&self, keyword: &str) -> Result<Vec<Quote>> {
+        let keyword = keyword.to_lowercase();
+        let quotes = self.storage.quotes()?;
+        Ok(quotes
+            .into_iter()
+            .filter(|quote| {
+                quote.text.to_lowercase().contains(&keyword)
+                    || quote.author.to_lowercase().contains(&keyword)
+                    || quote
+                        .game
+                        .as_ref()
+                        .is_some_and(|game| game.to_lowercase().contains(&keyword))
+            })
+            .collect())
+    }
+
+    pub fn edit(&self, id: u32, text: &str, author: Option<&str>) -> Result<Option<Quote>> {
+        let Some(mut quote) = self.storage.quote(id)? else {
+            return Ok(None);
+        };
+
+        quote.text = text.to_string();
+        if let Some(author) = author {
+            quote.author = author.to_string();
+        }
+        self.storage.update_quote(&quote)?;
+        Ok(Some(quote))
+    }
+
+    pub fn delete(&self, id: u32) -> Result<bool> {
+        self.storage.delete_quote(id)
+    }
+
+    pub fn export(&self) -> Result<usize> {
+        let quotes = self.storage.quotes()?;
+        if let Some(parent) = self.export_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.export_path, serde_json::to_string_pretty(&quotes)?)?;
+        Ok(quotes.len())
+    }
+
+    pub fn import(&self) -> Result<ImportSummary> {
+        let contents = fs::read_to_string(&self.export_path)?;
+        let mut imported: Vec<Quote> = serde_json::from_str(&contents)?;
+        imported.sort_by_key(|quote| quote.id);
+
+        let existing = self.storage.quotes()?;
+        let mut summary = ImportSummary::default();
+        for quote in imported {
+            let duplicate = existing
+                .iter()
+                .any(|other| other.text == quote.text && other.author == quote.author);
+            if duplicate {
+                continue;
+            }
+
+            if self.storage.insert_quote_with_id(&quote)? {
+                summary.imported += 1;
+            } else {
+                summary.collisions.push(quote.id);
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn book(storage: SharedStorage, path: &std::path::Path) -> QuoteBook {
+        QuoteBook::new(
+            &QuotesConfig {
+                export_file: path.to_str().unwrap().to_string(),
+            },
+            storage,
+        )
+    }
+
+    fn quote(text: &str) -> NewQuote {
+        NewQuote {
+            text: text.to_string(),
+            author: "april".to_string(),
+            game: None,
+            date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        }
+    }
+
+    #[test]
+    fn import_keeps_exported_ids() {
+        let path = std::env::temp_dir().join(format!("quotes-export-{}.json", std::process::id()));
+        let source = book(Arc::new(MemoryStorage::default()), &path);
+        for n in 1..=7 {
+            source.add(quote(&format!("quote {}", n))).unwrap();
+        }
+        source.delete(3).unwrap();
+        source.export().unwrap();
+
+        let target_storage: SharedStorage = Arc::new(MemoryStorage::default());
+        target_storage
+            .insert_quote_with_id(&Quote {
+                id: 2,
+                ..source.get(1).unwrap().unwrap()
+            })
+            .unwrap();
+        let target = book(Arc::clone(&target_storage), &path);
+        let summary = target.import().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(summary.imported, 4);
+        assert_eq!(summary.collisions, vec![2]);
+        assert_eq!(target.get(7).unwrap(), source.get(7).unwrap());
+        assert_eq!(target.get(3).unwrap(), None);
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use tracing::{error, info, warn};
+
+use super::{Quote, Repositories, Storage};
+use crate::error::Result;
+
+#[derive(Deserialize)]
+struct CommandsFile {
+    #[serde(default)]
+    commands: BTreeMap<String, String>,
+    #[serde(default)]
+    deleted: BTreeSet<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CountersFormat {
+    Tracked {
+        counts: BTreeMap<String, i64>,
+        #[serde(default)]
+        created: BTreeSet<String>,
+    },
+    Flat(BTreeMap<String, i64>),
+}
+
+#[derive(Deserialize)]
+#[serde(from = "CountersFormat")]
+struct CountersFile {
+    counts: BTreeMap<String, i64>,
+    created: BTreeSet<String>,
+}
+
+impl From<CountersFormat> for CountersFile {
+    fn from(format: CountersFormat) -> Self {
+        match format {
+            CountersFormat::Tracked { counts, created } => Self { counts, created },
+            CountersFormat::Flat(counts) => Self {
+                counts,
+                created: BTreeSet::new(),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct QuotesFile {
+    #[serde(default)]
+    quotes: Vec<Quote>,
+}
+
+fn import_file<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    dir: &Path,
+    name: &str,
+    apply: impl Fn(&dyn Repositories, &T) -> Result<()>,
+) -> Result<()> {
+    let path = dir.join(name);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+
+    let file: T = match serde_json::from_str(&contents) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!("Skipping legacy file {}: {}", path.display(), e);
+            return Ok(());
+        }
+    };
+
+    if let Err(e) = storage.transaction(&mut |transaction| apply(transaction, &file)) {
+        error!("Failed to import legacy file {}: {}", path.display(), e);
+        return Ok(());
+    }
+
+    fs::rename(&path, path.with_extension("json.imported"))?;
+    info!("Imported legacy data from {}", path.display());
+    Ok(())
+}
+
+pub fn import(storage: &dyn Storage, dir: &str) -> Result<()> {
+    let dir = Path::new(dir);
+
+    import_file(
+        storage,
+        dir,
+        "commands.json",
+        |transaction, file: &CommandsFile| {
+            for (name, response) in &file.commands {
+                transaction.set_custom_command(name, response)?;
+            }
+            for name in &file.deleted {
+                transaction.set_command_deleted(name, true)?;
+            }
+            Ok(())
+        },
+    )?;
+
+    import_file(
+        storage,
+        dir,
+        "counters.json",
+        |transaction, file: &CountersFile| {
+            for name in &file.created {
+                transaction.create_counter(name)?;
+            }
+            for (name, value) in &file.counts {
+                transaction.set_counter_value(name, *value)?;
+            }
+            Ok(())
+        },
+    )?;
+
+    import_file(
+        storage,
+        dir,
+        "quotes.json",
+        |transaction, file: &QuotesFile| {
+            for quote in &file.quotes {
+                if !transaction.insert_quote_with_id(quote)? {
+                    warn!("Skipping legacy quote #{}, the number is taken", quote.id);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{CounterRepository, MemoryStorage, QuoteRepository};
+
+    #[test]
+    fn imports_files_and_renames_them() {
+        let dir = std::env::temp_dir().join(format!("legacy-import-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("counters.json"), r#"{"deaths": 4}"#).unwrap();
+        fs::write(
+            dir.join("quotes.json"),
+            r#"{"quotes": [{"id": 7, "text": "gg", "author": "april", "date": "2025-01-02"}]}"#,
+        )
+        .unwrap();
+
+        let storage = MemoryStorage::default();
+        import(&storage, dir.to_str().unwrap()).unwrap();
+
+        assert_eq!(storage.counter_value("deaths").unwrap(), 4);
+        assert_eq!(storage.quote(7).unwrap().unwrap().text, "gg");
+        assert!(dir.join("quotes.json.imported").exists());
+        assert!(!dir.join("quotes.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_flat_counter_files() {
+        let file: CountersFile = serde_json::from_str(r#"{"deaths": 4, "wins": 2}"#).unwrap();
+
+        assert_eq!(
+            file.counts,
+            BTreeMap::from([("deaths".to_string(), 4), ("wins".to_string(), 2)])
+        );
+        assert!(file.created.is_empty());
+    }
+
+    #[test]
+    fn reads_tracked_counter_files() {
+        let file: CountersFile =
+            serde_json::from_str(r#"{"counts": {"deaths": 4}, "created": ["deaths"]}"#).unwrap();
+
+        assert_eq!(file.counts, BTreeMap::from([("deaths".to_string(), 4)]));
+        assert_eq!(file.created, BTreeSet::from(["deaths".to_string()]));
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+};
+
+use chrono::{DateTime, Utc};
+
+use super::{
+    CooldownRepository, CounterRepository, CustomCommandRepository, LoyaltyRepository, NewQuote,
+    Quote, QuoteRepository, Repositories, SongHistoryRepository, SongPlay, Transactional,
+    UserStats, UserStatsRepository,
+};
+use crate::error::Result;
+
+#[derive(Clone, Default)]
+struct State {
+    counters: BTreeMap<String, i64>,
+    created_counters: BTreeSet<String>,
+    quotes: BTreeMap<u32, Quote>,
+    last_quote_id: u32,
+    custom_commands: BTreeMap<String, String>,
+    deleted_commands: BTreeSet<String>,
+    songs: Vec<SongPlay>,
+    cooldowns: BTreeMap<String, DateTime<Utc>>,
+    users: BTreeMap<String, UserStats>,
+    loyalty: BTreeMap<String, (i64, u64)>,
+}
+
+#[derive(Default)]
+pub struct MemoryStorage {
+    state: Mutex<State>,
+}
+
+impl Transactional for MemoryStorage {
+    fn transaction(&self, work: &mut dyn FnMut(&dyn Repositories) -> Result<()>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let scratch = MemoryStorage {
+            state: Mutex::new(state.clone()),
+        };
+        work(&scratch)?;
+        *state = scratch.state.into_inner().unwrap();
+        Ok(())
+    }
+}
+
+impl CounterRepository for MemoryStorage {
+    fn counter_value(&self, name: &str) -> Result<i64> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .counters
+            .get(&name.to_lowercase())
+            .copied()
+            .unwrap_or(0))
+    }
+
+    fn set_counter_value(&self, name: &str, value: i64) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.counters.insert(name.to_lowercase(), value);
+        Ok(())
+    }
+
+    fn adjust_counter_value(&self, name: &str, delta: i64) -> Result<i64> {
+        let mut state = self.state.lock().unwrap();
+        let value = state.counters.entry(name.to_lowercase()).or_insert(0);
+        *value = value.saturating_add(delta);
+        Ok(*value)
+    }
+
+    fn created_counters(&self) -> Result<Vec<String>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.created_counters.iter().cloned().collect())
+    }
+
+    fn create_counter(&self, name: &str) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        Ok(state.created_counters.insert(name.to_lowercase()))
+    }
+
+    fn delete_counter(&self, name: &str) -> Result<bool> {
+        let name = name.to_lowercase();
+        let mut state = self.state.lock().unwrap();
+        if !state.created_counters.remove(&name) {
+            return Ok(false);
+        }
+        state.counters.remove(&name);
+        Ok(true)
+    }
+}
+
+impl QuoteRepository for MemoryStorage {
+    fn insert_quote(&self, quote: NewQuote) -> Result<Quote> {
+        let mut state = self.state.lock().unwrap();
+        state.last_quote_id += 1;
+        let quote = Quote {
+            id: state.last_quote_id,
+            text: quote.text,
+            author: quote.author,
+            game: quote.game,
+            date: quote.date,
+        };
+        state.quotes.insert(quote.id, quote.clone());
+        Ok(quote)
+    }
+
+    fn insert_quote_with_id(&self, quote: &Quote) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        if state.quotes.contains_key(&quote.id) {
+            return Ok(false);
+        }
+        state.last_quote_id = state.last_quote_id.max(quote.id);
+        state.quotes.insert(quote.id, quote.clone());
+        Ok(true)
+    }
+
+    fn quote(&self, id: u32) -> Result<Option<Quote>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.quotes.get(&id).cloned())
+    }
+
+    fn quotes(&self) -> Result<Vec<Quote>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.quotes.values().cloned().collect())
+    }
+
+    fn update_quote(&self, quote: &Quote) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        match state.quotes.get_mut(&quote.id) {
+            Some(existing) => {
+                *existing = quote.clone();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn delete_quote(&self, id: u32) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        Ok(state.quotes.remove(&id).is_some())
+    }
+}
+
+impl CustomCommandRepository for MemoryStorage {
+    fn custom_commands(&self) -> Result<Vec<(String, String)>> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .custom_commands
+            .iter()
+            .map(|(name, response)| (name.clone(), response.clone()))
+            .collect())
+    }
+
+    fn set_custom_command(&self, name: &str, response: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .custom_commands
+            .insert(name.to_string(), response.to_string());
+        Ok(())
+    }
+
+    fn delete_custom_command(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.custom_commands.remove(name);
+        Ok(())
+    }
+
+    fn deleted_commands(&self) -> Result<Vec<String>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.deleted_commands.iter().cloned().collect())
+    }
+
+    fn set_command_deleted(&self, name: &str, deleted: bool) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if deleted {
+            state.deleted_commands.insert(name.to_string());
+        } else {
+            state.deleted_commands.remove(name);
+        }
+        Ok(())
+    }
+}
+
+impl SongHistoryRepository for MemoryStorage {
+    fn record_song(&self, play: &SongPlay) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.songs.push(play.clone());
+        Ok(())
+    }
+
+    fn recent_songs(&self, limit: usize) -> Result<Vec<SongPlay>> {
+        let state = self.state.lock().unwrap();
+        let mut songs = state.songs.clone();
+        songs.sort_by_key(|play| Reverse(play.played_at));
+        songs.truncate(limit);
+        Ok(songs)
+    }
+}
+
+impl CooldownRepository for MemoryStorage {
+    fn cooldown(&self, key: &str) -> Result<Option<DateTime<Utc>>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.cooldowns.get(key).copied())
+    }
+
+    fn set_cooldown(&self, key: &str, used_at: DateTime<Utc>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.cooldowns.insert(key.to_string(), used_at);
+        Ok(())
+    }
+}
+
+impl UserStatsRepository for MemoryStorage {
+    fn record_chat(&self, login: &str, at: DateTime<Utc>) -> Result<UserStats> {
+        let login = login.to_lowercase();
+        let mut state = self.state.lock().unwrap();
+        let stats = state.users.entry(login.clone()).or_insert(UserStats {
+            login,
+            messages: 0,
+            first_seen: at,
+            last_seen: at,
+        });
+        stats.messages += 1;
+        stats.last_seen = at;
+        Ok(stats.clone())
+    }
+
+    fn user_stats(&self, login: &str) -> Result<Option<UserStats>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.users.get(&login.to_lowercase()).cloned())
+    }
+}
+
+impl LoyaltyRepository for MemoryStorage {
+    fn points(&self, login: &str) -> Result<i64> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .loyalty
+            .get(&login.to_lowercase())
+            .map_or(0, |(points, _)| *points))
+    }
+
+    fn add_points(&self, login: &str, delta: i64) -> Result<i64> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.loyalty.entry(login.to_lowercase()).or_default();
+        entry.0 = entry.0.saturating_add(delta);
+        Ok(entry.0)
+    }
+
+    fn spend_points(&self, login: &str, cost: i64) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        match state.loyalty.get_mut(&login.to_lowercase()) {
+            Some((points, _)) if *points >= cost => {
+                *points -= cost;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn watch_time(&self, login: &str) -> Result<u64> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .loyalty
+            .get(&login.to_lowercase())
+            .map_or(0, |(_, seconds)| *seconds))
+    }
+
+    fn accrue(&self, login: &str, points: i64, seconds: u64) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.loyalty.entry(login.to_lowercase()).or_default();
+        entry.0 = entry.0.saturating_add(points);
+        entry.1 = entry.1.saturating_add(seconds);
+        Ok(())
+    }
+
+    fn top_points(&self, limit: usize) -> Result<Vec<(String, i64)>> {
+        let state = self.state.lock().unwrap();
+        let mut top: Vec<(String, i64)> = state
+            .loyalty
+            .iter()
+            .map(|(login, (points, _))| (login.clone(), *points))
+            .collect();
+        top.sort_by_key(|(_, points)| Reverse(*points));
+        top.truncate(limit);
+        Ok(top)
+    }
+
+    fn top_watch_time(&self, limit: usize) -> Result<Vec<(String, u64)>> {
+        let state = self.state.lock().unwrap();
+        let mut top: Vec<(String, u64)> = state
+            .loyalty
+            .iter()
+            .map(|(login, (_, seconds))| (login.clone(), *seconds))
+            .collect();
+        top.sort_by_key(|(_, seconds)| Reverse(*seconds));
+        top.truncate(limit);
+        Ok(top)
+    }
+}
@@ -0,0 +1,389 @@
+mod legacy;
+mod memory;
+mod sqlite;
+
+use std::{path::Path, sync::Arc};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{config::Config, error::Result};
+
+pub use memory::MemoryStorage;
+pub use sqlite::SqliteStorage;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quote {
+    pub id: u32,
+    pub text: String,
+    pub author: String,
+    #[serde(default)]
+    pub game: Option<String>,
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewQuote {
+    pub text: String,
+    pub author: String,
+    pub game: Option<String>,
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SongPlay {
+    pub track: String,
+    pub requested_by: Option<String>,
+    pub played_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserStats {
+    pub login: String,
+    pub messages: u64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+pub trait CounterRepository {
+    fn counter_value(&self, name: &str) -> Result<i64>;
+    fn set_counter_value(&self, name: &str, value: i64) -> Result<()>;
+    fn adjust_counter_value(&self, name: &str, delta: i64) -> Result<i64>;
+    fn created_counters(&self) -> Result<Vec<String>>;
+    fn create_counter(&self, name: &str) -> Result<bool>;
+    fn delete_counter(&self, name: &str) -> Result<bool>;
+}
+
+pub trait QuoteRepository {
+    fn insert_quote(&self, quote: NewQuote) -> Result<Quote>;
+    fn insert_quote_with_id(&self, quote: &Quote) -> Result<bool>;
+    fn quote(&self, id: u32) -> Result<Option<Quote>>;
+    fn quotes(&self) -> Result<Vec<Quote>>;
+    fn update_quote(&self, quote: &Quote) -> Result<bool>;
+    fn delete_quote(&self, id: u32) -> Result<bool>;
+}
+
+pub trait CustomCommandRepository {
+    fn custom_commands(&self) -> Result<Vec<(String, String)>>;
+    fn set_custom_command(&self, name: &str, response: &str) -> Result<()>;
+    fn delete_custom_command(&self, name: &str) -> Result<()>;
+    fn deleted_commands(&self) -> Result<Vec<String>>;
+    fn set_command_deleted(&self, name: &str, deleted: bool) -> Result<()>;
+}
+
+pub trait SongHistoryRepository {
+    fn record_song(&self, play: &SongPlay) -> Result<()>;
+    fn recent_songs(&self, limit: usize) -> Result<Vec<SongPlay>>;
+}
+
+pub trait CooldownRepository {
+    fn cooldown(&self, key: &str) -> Result<Option<DateTime<Utc>>>;
+    fn set_cooldown(&self, key: &str, used_at: DateTime<Utc>) -> Result<()>;
+}
+
+pub trait UserStatsRepository {
+    fn record_chat(&self, login: &str, at: DateTime<Utc>) -> Result<UserStats>;
+    fn user_stats(&self, login: &str) -> Result<Option<UserStats>>;
+}
+
+pub trait LoyaltyRepository {
+    fn points(&self, login: &str) -> Result<i64>;
+    fn add_points(&self, login: &str, delta: i64) -> Result<i64>;
+    fn spend_points(&self, login: &str, cost: i64) -> Result<bool>;
+    fn watch_time(&self, login: &str) -> Result<u64>;
+    fn accrue(&self, login: &str, points: i64, seconds: u64) -> Result<()>;
+    fn top_points(&self, limit: usize) -> Result<Vec<(String, i64)>>;
+    fn top_watch_time(&self, limit: usize) -> Result<Vec<(String, u64)>>;
+}
+
+pub trait Repositories:
+    CounterRepository
+    + QuoteRepository
+    + CustomCommandRepository
+    + SongHistoryRepository
+    + CooldownRepository
+    + UserStatsRepository
+    + LoyaltyRepository
+{
+}
+
+impl<T> Repositories for T where
+    T: CounterRepository
+        + QuoteRepository
+        + CustomCommandRepository
+        + SongHistoryRepository
+        + CooldownRepository
+        + UserStatsRepository
+        + LoyaltyRepository
+{
+}
+
+pub trait Transactional {
+    fn transaction(&self, work: &mut dyn FnMut(&dyn Repositories) -> Result<()>) -> Result<()>;
+}
+
+pub trait Storage: Repositories + Transactional + Send + Sync {}
+
+impl<T> Storage for T where T: Repositories + Transactional + Send + Sync {}
+
+pub type SharedStorage = Arc<dyn Storage>;
+
+pub fn open(config: &Config) -> Result<SharedStorage> {
+    let path = Path::new(&config.storage.path);
+    let storage: SharedStorage = if config.storage.path == ":memory:" {
+        Arc::new(MemoryStorage::default())
+    } else {
+        Arc::new(SqliteStorage::open(path)?)
+    };
+
+    info!("Opened storage at {}", config.storage.path);
+    legacy::import(storage.as_ref(), &config.storage.legacy_dir)?;
+    Ok(storage)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::error::BotError;
+
+    fn storages() -> Vec<(&'static str, Box<dyn Storage>)> {
+        vec![
+            ("memory", Box::new(MemoryStorage::default())),
+            ("sqlite", Box::new(SqliteStorage::open_in_memory().unwrap())),
+        ]
+    }
+
+    fn new_quote(text: &str) -> NewQuote {
+        NewQuote {
+            text: text.to_string(),
+            author: "april".to_string(),
+            game: Some("Valorant".to_string()),
+            date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        }
+    }
+
+    #[test]
+    fn counters_default_to_zero_and_adjust() {
+        for (kind, storage) in storages() {
+            assert_eq!(storage.counter_value("deaths").unwrap(), 0, "{}", kind);
+            assert_eq!(storage.adjust_counter_value("deaths", 2).unwrap(), 2);
+            assert_eq!(storage.adjust_counter_value("Deaths", -1).unwrap(), 1);
+            storage.set_counter_value("deaths", 10).unwrap();
+            assert_eq!(storage.counter_value("deaths").unwrap(), 10, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn created_counters_can_be_deleted() {
+        for (kind, storage) in storages() {
+            assert!(storage.create_counter("wins").unwrap(), "{}", kind);
+            assert!(!storage.create_counter("wins").unwrap(), "{}", kind);
+            storage.adjust_counter_value("wins", 3).unwrap();
+            assert_eq!(storage.created_counters().unwrap(), vec!["wins"]);
+
+            assert!(storage.delete_counter("wins").unwrap(), "{}", kind);
+            assert!(!storage.delete_counter("wins").unwrap(), "{}", kind);
+            assert!(storage.created_counters().unwrap().is_empty());
+            assert_eq!(storage.counter_value("wins").unwrap(), 0, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn quotes_round_trip() {
+        for (kind, storage) in storages() {
+            let first = storage.insert_quote(new_quote("first")).unwrap();
+            let second = storage.insert_quote(new_quote("second")).unwrap();
+            assert_eq!((first.id, second.id), (1, 2), "{}", kind);
+            assert_eq!(storage.quote(1).unwrap(), Some(first.clone()));
+
+            let mut edited = second.clone();
+            edited.text = "edited".to_string();
+            assert!(storage.update_quote(&edited).unwrap(), "{}", kind);
+            assert_eq!(storage.quote(2).unwrap().unwrap().text, "edited");
+
+            assert!(storage.delete_quote(1).unwrap(), "{}", kind);
+            assert!(!storage.delete_quote(1).unwrap(), "{}", kind);
+            assert_eq!(storage.quotes().unwrap(), vec![edited], "{}", kind);
+        }
+    }
+
+    #[test]
+    fn deleted_quote_numbers_are_not_reused() {
+        for (kind, storage) in storages() {
+            storage.insert_quote(new_quote("first")).unwrap();
+            let second = storage.insert_quote(new_quote("second")).unwrap();
+            storage.delete_quote(second.id).unwrap();
+            let third = storage.insert_quote(new_quote("third")).unwrap();
+            assert_eq!(third.id, 3, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn quotes_keep_explicit_ids() {
+        for (kind, storage) in storages() {
+            let mut quote = storage.insert_quote(new_quote("first")).unwrap();
+            quote.id = 7;
+            quote.text = "seventh".to_string();
+            assert!(storage.insert_quote_with_id(&quote).unwrap(), "{}", kind);
+            assert!(!storage.insert_quote_with_id(&quote).unwrap(), "{}", kind);
+            assert_eq!(storage.quote(7).unwrap(), Some(quote), "{}", kind);
+
+            let next = storage.insert_quote(new_quote("next")).unwrap();
+            assert_eq!(next.id, 8, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn failed_transactions_roll_back() {
+        for (kind, storage) in storages() {
+            storage.set_counter_value("deaths", 1).unwrap();
+            let result = storage.transaction(&mut |transaction| {
+                transaction.set_counter_value("deaths", 5)?;
+                transaction.insert_quote(new_quote("first"))?;
+                Err(BotError::Config("boom".to_string()))
+            });
+
+            assert!(result.is_err(), "{}", kind);
+            assert_eq!(storage.counter_value("deaths").unwrap(), 1, "{}", kind);
+            assert!(storage.quotes().unwrap().is_empty(), "{}", kind);
+
+            storage
+                .transaction(&mut |transaction| transaction.set_counter_value("deaths", 5))
+                .unwrap();
+            assert_eq!(storage.counter_value("deaths").unwrap(), 5, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn rollbacks_keep_concurrent_writes() {
+        for (kind, storage) in storages() {
+            let storage = storage.as_ref();
+            thread::scope(|scope| {
+                let result = storage.transaction(&mut |transaction| {
+                    transaction.set_counter_value("deaths", 5)?;
+                    scope.spawn(|| storage.set_counter_value("wins", 3).unwrap());
+                    thread::sleep(Duration::from_millis(50));
+                    Err(BotError::Config("boom".to_string()))
+                });
+                assert!(result.is_err(), "{}", kind);
+            });
+
+            assert_eq!(storage.counter_value("deaths").unwrap(), 0, "{}", kind);
+            assert_eq!(storage.counter_value("wins").unwrap(), 3, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn custom_commands_and_tombstones() {
+        for (kind, storage) in storages() {
+            storage.set_custom_command("dpi", "800").unwrap();
+            storage.set_custom_command("dpi", "1600").unwrap();
+            assert_eq!(
+                storage.custom_commands().unwrap(),
+                vec![("dpi".to_string(), "1600".to_string())],
+                "{}",
+                kind
+            );
+            storage.delete_custom_command("dpi").unwrap();
+            assert!(storage.custom_commands().unwrap().is_empty(), "{}", kind);
+
+            storage.set_command_deleted("sens", true).unwrap();
+            assert_eq!(storage.deleted_commands().unwrap(), vec!["sens"]);
+            storage.set_command_deleted("sens", false).unwrap();
+            assert!(storage.deleted_commands().unwrap().is_empty(), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn song_history_is_newest_first() {
+        for (kind, storage) in storages() {
+            for (minute, track) in ["a", "b", "c"].iter().enumerate() {
+                storage
+                    .record_song(&SongPlay {
+                        track: track.to_string(),
+                        requested_by: None,
+                        played_at: Utc
+                            .with_ymd_and_hms(2025, 1, 1, 0, minute as u32, 0)
+                            .unwrap(),
+                    })
+                    .unwrap();
+            }
+
+            let tracks: Vec<String> = storage
+                .recent_songs(2)
+                .unwrap()
+                .into_iter()
+                .map(|play| play.track)
+                .collect();
+            assert_eq!(tracks, vec!["c", "b"], "{}", kind);
+        }
+    }
+
+    #[test]
+    fn cooldowns_round_trip() {
+        for (kind, storage) in storages() {
+            let at = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+            assert_eq!(storage.cooldown("so:1").unwrap(), None, "{}", kind);
+            storage.set_cooldown("so:1", at).unwrap();
+            assert_eq!(storage.cooldown("so:1").unwrap(), Some(at), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn loyalty_accrues_and_ranks() {
+        for (kind, storage) in storages() {
+            storage.accrue("April", 10, 300).unwrap();
+            storage.accrue("april", 15, 300).unwrap();
+            storage.accrue("lurker", 5, 900).unwrap();
+            assert_eq!(storage.points("april").unwrap(), 25, "{}", kind);
+            assert_eq!(storage.watch_time("APRIL").unwrap(), 600, "{}", kind);
+            assert_eq!(storage.points("nobody").unwrap(), 0, "{}", kind);
+
+            assert_eq!(storage.add_points("lurker", 100).unwrap(), 105, "{}", kind);
+            assert_eq!(
+                storage.top_points(1).unwrap(),
+                vec![("lurker".to_string(), 105)],
+                "{}",
+                kind
+            );
+            assert_eq!(
+                storage.top_watch_time(2).unwrap(),
+                vec![("lurker".to_string(), 900), ("april".to_string(), 600)],
+                "{}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn spending_requires_enough_points() {
+        for (kind, storage) in storages() {
+            assert!(!storage.spend_points("april", 10).unwrap(), "{}", kind);
+            storage.add_points("april", 15).unwrap();
+            assert!(storage.spend_points("april", 10).unwrap(), "{}", kind);
+            assert!(!storage.spend_points("april", 10).unwrap(), "{}", kind);
+            assert_eq!(storage.points("april").unwrap(), 5, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn user_stats_accumulate() {
+        for (kind, storage) in storages() {
+            let first = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+            let later = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+            storage.record_chat("April", first).unwrap();
+            let stats = storage.record_chat("april", later).unwrap();
+
+            assert_eq!(stats.messages, 2, "{}", kind);
+            assert_eq!(stats.first_seen, first, "{}", kind);
+            assert_eq!(stats.last_seen, later, "{}", kind);
+            assert_eq!(storage.user_stats("april").unwrap(), Some(stats));
+            assert_eq!(storage.user_stats("nobody").unwrap(), None, "{}", kind);
+        }
+    }
+}
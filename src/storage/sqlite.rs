@@ -0,0 +1,484 @@
+use std::{ops::Deref, path::Path, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, Row, TransactionBehavior, params};
+use tracing::info;
+
+use super::{
+    CooldownRepository, CounterRepository, CustomCommandRepository, LoyaltyRepository, NewQuote,
+    Quote, QuoteRepository, Repositories, SongHistoryRepository, SongPlay, Transactional,
+    UserStats, UserStatsRepository,
+};
+use crate::error::Result;
+
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE counters (
+        name TEXT PRIMARY KEY,
+        value INTEGER NOT NULL DEFAULT 0,
+        created INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE quotes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        text TEXT NOT NULL,
+        author TEXT NOT NULL,
+        game TEXT,
+        date TEXT NOT NULL
+    );
+    CREATE TABLE custom_commands (
+        name TEXT PRIMARY KEY,
+        response TEXT NOT NULL
+    );
+    CREATE TABLE deleted_commands (
+        name TEXT PRIMARY KEY
+    );
+    CREATE TABLE song_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        track TEXT NOT NULL,
+        requested_by TEXT,
+        played_at TEXT NOT NULL
+    );
+    CREATE TABLE cooldowns (
+        key TEXT PRIMARY KEY,
+        used_at TEXT NOT NULL
+    );
+    CREATE TABLE user_stats (
+        login TEXT PRIMARY KEY,
+        messages INTEGER NOT NULL DEFAULT 0,
+        first_seen TEXT NOT NULL,
+        last_seen TEXT NOT NULL
+    );",
+    "CREATE TABLE loyalty (
+        login TEXT PRIMARY KEY,
+        points INTEGER NOT NULL DEFAULT 0,
+        watch_seconds INTEGER NOT NULL DEFAULT 0
+    );",
+];
+
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut connection: Connection) -> Result<Self> {
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "foreign_keys", "ON")?;
+        Self::migrate(&mut connection)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn migrate(connection: &mut Connection) -> Result<()> {
+        let version: usize =
+            connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(migration)?;
+            transaction.pragma_update(None, "user_version", index + 1)?;
+            transaction.commit()?;
+            info!("Applied storage migration {}", index + 1);
+        }
+
+        Ok(())
+    }
+
+    fn quote_from_row(row: &Row) -> rusqlite::Result<Quote> {
+        Ok(Quote {
+            id: row.get("id")?,
+            text: row.get("text")?,
+            author: row.get("author")?,
+            game: row.get("game")?,
+            date: row.get("date")?,
+        })
+    }
+
+    fn user_stats_from_row(row: &Row) -> rusqlite::Result<UserStats> {
+        Ok(UserStats {
+            login: row.get("login")?,
+            messages: row.get("messages")?,
+            first_seen: row.get("first_seen")?,
+            last_seen: row.get("last_seen")?,
+        })
+    }
+}
+
+trait Connected {
+    fn connection(&self) -> impl Deref<Target = Connection>;
+}
+
+impl Connected for SqliteStorage {
+    fn connection(&self) -> impl Deref<Target = Connection> {
+        self.connection.lock().unwrap()
+    }
+}
+
+struct SqliteTransaction<'a> {
+    connection: &'a Connection,
+}
+
+impl Connected for SqliteTransaction<'_> {
+    fn connection(&self) -> impl Deref<Target = Connection> {
+        self.connection
+    }
+}
+
+impl Transactional for SqliteStorage {
+    fn transaction(&self, work: &mut dyn FnMut(&dyn Repositories) -> Result<()>) -> Result<()> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        work(&SqliteTransaction {
+            connection: &transaction,
+        })?;
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+impl<C: Connected> CounterRepository for C {
+    fn counter_value(&self, name: &str) -> Result<i64> {
+        let connection = self.connection();
+        let value = connection
+            .query_row(
+                "SELECT value FROM counters WHERE name = ?1",
+                [name.to_lowercase()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value.unwrap_or(0))
+    }
+
+    fn set_counter_value(&self, name: &str, value: i64) -> Result<()> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO counters (name, value) VALUES (?1, ?2)
+             ON CONFLICT (name) DO UPDATE SET value = excluded.value",
+            params![name.to_lowercase(), value],
+        )?;
+        Ok(())
+    }
+
+    fn adjust_counter_value(&self, name: &str, delta: i64) -> Result<i64> {
+        let connection = self.connection();
+        let value = connection.query_row(
+            "INSERT INTO counters (name, value) VALUES (?1, ?2)
+             ON CONFLICT (name) DO UPDATE SET value = value + excluded.value
+             RETURNING value",
+            params![name.to_lowercase(), delta],
+            |row| row.get(0),
+        )?;
+        Ok(value)
+    }
+
+    fn created_counters(&self) -> Result<Vec<String>> {
+        let connection = self.connection();
+        let mut statement =
+            connection.prepare("SELECT name FROM counters WHERE created = 1 ORDER BY name")?;
+        let names = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(names)
+    }
+
+    fn create_counter(&self, name: &str) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute(
+            "INSERT INTO counters (name, created) VALUES (?1, 1)
+             ON CONFLICT (name) DO UPDATE SET created = 1 WHERE created = 0",
+            [name.to_lowercase()],
+        )?;
+        Ok(changed > 0)
+    }
+
+    fn delete_counter(&self, name: &str) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute(
+            "DELETE FROM counters WHERE name = ?1 AND created = 1",
+            [name.to_lowercase()],
+        )?;
+        Ok(changed > 0)
+    }
+}
+
+impl<C: Connected> QuoteRepository for C {
+    fn insert_quote(&self, quote: NewQuote) -> Result<Quote> {
+        let connection = self.connection();
+        let quote = connection.query_row(
+            "INSERT INTO quotes (text, author, game, date) VALUES (?1, ?2, ?3, ?4)
+             RETURNING *",
+            params![quote.text, quote.author, quote.game, quote.date],
+            SqliteStorage::quote_from_row,
+        )?;
+        Ok(quote)
+    }
+
+    fn insert_quote_with_id(&self, quote: &Quote) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute(
+            "INSERT OR IGNORE INTO quotes (id, text, author, game, date)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![quote.id, quote.text, quote.author, quote.game, quote.date],
+        )?;
+        Ok(changed > 0)
+    }
+
+    fn quote(&self, id: u32) -> Result<Option<Quote>> {
+        let connection = self.connection();
+        let quote = connection
+            .query_row(
+                "SELECT * FROM quotes WHERE id = ?1",
+                [id],
+                SqliteStorage::quote_from_row,
+            )
+            .optional()?;
+        Ok(quote)
+    }
+
+    fn quotes(&self) -> Result<Vec<Quote>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare("SELECT * FROM quotes ORDER BY id")?;
+        let quotes = statement
+            .query_map([], SqliteStorage::quote_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(quotes)
+    }
+
+    fn update_quote(&self, quote: &Quote) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute(
+            "UPDATE quotes SET text = ?2, author = ?3, game = ?4, date = ?5 WHERE id = ?1",
+            params![quote.id, quote.text, quote.author, quote.game, quote.date],
+        )?;
+        Ok(changed > 0)
+    }
+
+    fn delete_quote(&self, id: u32) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute("DELETE FROM quotes WHERE id = ?1", [id])?;
+        Ok(changed > 0)
+    }
+}
+
+impl<C: Connected> CustomCommandRepository for C {
+    fn custom_commands(&self) -> Result<Vec<(String, String)>> {
+        let connection = self.connection();
+        let mut statement =
+            connection.prepare("SELECT name, response FROM custom_commands ORDER BY name")?;
+        let commands = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(commands)
+    }
+
+    fn set_custom_command(&self, name: &str, response: &str) -> Result<()> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO custom_commands (name, response) VALUES (?1, ?2)
+             ON CONFLICT (name) DO UPDATE SET response = excluded.response",
+            params![name, response],
+        )?;
+        Ok(())
+    }
+
+    fn delete_custom_command(&self, name: &str) -> Result<()> {
+        let connection = self.connection();
+        connection.execute("DELETE FROM custom_commands WHERE name = ?1", [name])?;
+        Ok(())
+    }
+
+    fn deleted_commands(&self) -> Result<Vec<String>> {
+        let connection = self.connection();
+        let mut statement =
+            connection.prepare("SELECT name FROM deleted_commands ORDER BY name")?;
+        let names = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(names)
+    }
+
+    fn set_command_deleted(&self, name: &str, deleted: bool) -> Result<()> {
+        let connection = self.connection();
+        if deleted {
+            connection.execute(
+                "INSERT OR IGNORE INTO deleted_commands (name) VALUES (?1)",
+                [name],
+            )?;
+        } else {
+            connection.execute("DELETE FROM deleted_commands WHERE name = ?1", [name])?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: Connected> SongHistoryRepository for C {
+    fn record_song(&self, play: &SongPlay) -> Result<()> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO song_history (track, requested_by, played_at) VALUES (?1, ?2, ?3)",
+            params![play.track, play.requested_by, play.played_at],
+        )?;
+        Ok(())
+    }
+
+    fn recent_songs(&self, limit: usize) -> Result<Vec<SongPlay>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT track, requested_by, played_at FROM song_history
+             ORDER BY played_at DESC, id DESC LIMIT ?1",
+        )?;
+        let songs = statement
+            .query_map([limit as i64], |row| {
+                Ok(SongPlay {
+                    track: row.get(0)?,
+                    requested_by: row.get(1)?,
+                    played_at: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(songs)
+    }
+}
+
+impl<C: Connected> CooldownRepository for C {
+    fn cooldown(&self, key: &str) -> Result<Option<DateTime<Utc>>> {
+        let connection = self.connection();
+        let used_at = connection
+            .query_row(
+                "SELECT used_at FROM cooldowns WHERE key = ?1",
+                [key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(used_at)
+    }
+
+    fn set_cooldown(&self, key: &str, used_at: DateTime<Utc>) -> Result<()> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO cooldowns (key, used_at) VALUES (?1, ?2)
+             ON CONFLICT (key) DO UPDATE SET used_at = excluded.used_at",
+            params![key, used_at],
+        )?;
+        Ok(())
+    }
+}
+
+impl<C: Connected> UserStatsRepository for C {
+    fn record_chat(&self, login: &str, at: DateTime<Utc>) -> Result<UserStats> {
+        let connection = self.connection();
+        let stats = connection.query_row(
+            "INSERT INTO user_stats (login, messages, first_seen, last_seen) VALUES (?1, 1, ?2, ?2)
+             ON CONFLICT (login) DO UPDATE SET
+                messages = messages + 1,
+                last_seen = excluded.last_seen
+             RETURNING *",
+            params![login.to_lowercase(), at],
+            SqliteStorage::user_stats_from_row,
+        )?;
+        Ok(stats)
+    }
+
+    fn user_stats(&self, login: &str) -> Result<Option<UserStats>> {
+        let connection = self.connection();
+        let stats = connection
+            .query_row(
+                "SELECT * FROM user_stats WHERE login = ?1",
+                [login.to_lowercase()],
+                SqliteStorage::user_stats_from_row,
+            )
+            .optional()?;
+        Ok(stats)
+    }
+}
+
+impl<C: Connected> LoyaltyRepository for C {
+    fn points(&self, login: &str) -> Result<i64> {
+        let connection = self.connection();
+        let points = connection
+            .query_row(
+                "SELECT points FROM loyalty WHERE login = ?1",
+                [login.to_lowercase()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(points.unwrap_or(0))
+    }
+
+    fn add_points(&self, login: &str, delta: i64) -> Result<i64> {
+        let connection = self.connection();
+        let points = connection.query_row(
+            "INSERT INTO loyalty (login, points) VALUES (?1, ?2)
+             ON CONFLICT (login) DO UPDATE SET points = points + excluded.points
+             RETURNING points",
+            params![login.to_lowercase(), delta],
+            |row| row.get(0),
+        )?;
+        Ok(points)
+    }
+
+    fn spend_points(&self, login: &str, cost: i64) -> Result<bool> {
+        let connection = self.connection();
+        let changed = connection.execute(
+            "UPDATE loyalty SET points = points - ?2 WHERE login = ?1 AND points >= ?2",
+            params![login.to_lowercase(), cost],
+        )?;
+        Ok(changed > 0)
+    }
+
+    fn watch_time(&self, login: &str) -> Result<u64> {
+        let connection = self.connection();
+        let seconds = connection
+            .query_row(
+                "SELECT watch_seconds FROM loyalty WHERE login = ?1",
+                [login.to_lowercase()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(seconds.unwrap_or(0))
+    }
+
+    fn accrue(&self, login: &str, points: i64, seconds: u64) -> Result<()> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO loyalty (login, points, watch_seconds) VALUES (?1, ?2, ?3)
+             ON CONFLICT (login) DO UPDATE SET
+                points = points + excluded.points,
+                watch_seconds = watch_seconds + excluded.watch_seconds",
+            params![login.to_lowercase(), points, seconds],
+        )?;
+        Ok(())
+    }
+
+    fn top_points(&self, limit: usize) -> Result<Vec<(String, i64)>> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare("SELECT login, points FROM loyalty ORDER BY points DESC, login LIMIT ?1")?;
+        let top = statement
+            .query_map([limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(top)
+    }
+
+    fn top_watch_time(&self, limit: usize) -> Result<Vec<(String, u64)>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT login, watch_seconds FROM loyalty ORDER BY watch_seconds DESC, login LIMIT ?1",
+        )?;
+        let top = statement
+            .query_map([limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(top)
+    }
+}
@@ -0,0 +1,267 @@
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable {
+        name: String,
+        arg: Option<String>,
+        source: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut inner = String::new();
+                    let mut closed = false;
+
+                    while let Some(&next) = chars.peek() {
+                        if next == '{' {
+                            break;
+                        }
+                        chars.next();
+                        if next == '}' {
+                            closed = true;
+                            break;
+                        }
+                        inner.push(next);
+                    }
+
+                    if closed && !inner.trim().is_empty() {
+                        if !text.is_empty() {
+                            segments.push(Segment::Text(std::mem::take(&mut text)));
+                        }
+                        let (name, arg) = match inner.split_once(':') {
+                            Some((name, arg)) => (name, Some(arg.to_string())),
+                            None => (inner.as_str(), None),
+                        };
+                        segments.push(Segment::Variable {
+                            name: name.trim().to_lowercase(),
+                            arg,
+                            source: inner,
+                        });
+                    } else {
+                        text.push('{');
+                        text.push_str(&inner);
+                        if closed {
+                            text.push('}');
+                        }
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Self { segments }
+    }
+
+    pub fn uses(&self, variable: &str) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Variable { name, .. } if name == variable))
+    }
+
+    pub fn render<F>(&self, mut resolve: F) -> String
+    where
+        F: FnMut(&str, Option<&str>) -> Option<String>,
+    {
+        let mut output = String::new();
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable { name, arg, source } => match resolve(name, arg.as_deref()) {
+                    Some(value) => output.push_str(&value),
+                    None => {
+                        output.push('{');
+                        output.push_str(source);
+                        output.push('}');
+                    }
+                },
+            }
+        }
+
+        output
+    }
+}
+
+fn parse_range(range: &str) -> Option<(i64, i64)> {
+    let range = range.trim();
+    range
+        .match_indices('-')
+        .filter(|(index, _)| *index > 0)
+        .find_map(|(index, _)| {
+            let min = range[..index].trim().parse().ok()?;
+            let max = range[index + 1..].trim().parse().ok()?;
+            Some((min, max))
+        })
+}
+
+pub fn builtin<R: Rng + ?Sized>(name: &str, arg: Option<&str>, rng: &mut R) -> Option<String> {
+    match name {
+        "random" => {
+            let (min, max) = match arg {
+                Some(range) => parse_range(range)?,
+                None => (1, 100),
+            };
+            let (min, max) = if min <= max { (min, max) } else { (max, min) };
+            Some(rng.random_range(min..=max).to_string())
+        }
+        "pick" => {
+            let options: Vec<&str> = arg?
+                .split('|')
+                .map(str::trim)
+                .filter(|option| !option.is_empty())
+                .collect();
+            if options.is_empty() {
+                return None;
+            }
+            Some(options[rng.random_range(0..options.len())].to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn render(source: &str) -> String {
+        Template::parse(source).render(|name, arg| match (name, arg) {
+            ("user", None) => Some("april".to_string()),
+            ("1", None) => Some("first".to_string()),
+            ("echo", Some(arg)) => Some(arg.to_string()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn renders_plain_text_unchanged() {
+        assert_eq!(render("just text :3"), "just text :3");
+    }
+
+    #[test]
+    fn substitutes_variables() {
+        assert_eq!(
+            render("hi {user}, you said {1}"),
+            "hi april, you said first"
+        );
+    }
+
+    #[test]
+    fn variable_names_are_case_insensitive() {
+        assert_eq!(render("{USER}"), "april");
+    }
+
+    #[test]
+    fn passes_arguments() {
+        assert_eq!(render("{echo:a|b:c}"), "a|b:c");
+    }
+
+    #[test]
+    fn unknown_variables_render_literally() {
+        assert_eq!(render("{nope} and {nope:arg}"), "{nope} and {nope:arg}");
+        assert_eq!(render("{NoPe} and { foo :Arg}"), "{NoPe} and { foo :Arg}");
+    }
+
+    #[test]
+    fn doubled_braces_escape() {
+        assert_eq!(render("{{user}} is {user}"), "{user} is april");
+        assert_eq!(render("}} {{"), "} {");
+    }
+
+    #[test]
+    fn unclosed_and_empty_braces_are_literal() {
+        assert_eq!(render("{user"), "{user");
+        assert_eq!(render("{} {user}"), "{} april");
+        assert_eq!(render("{ {user}"), "{ april");
+    }
+
+    #[test]
+    fn substituted_values_are_not_reparsed() {
+        let output = Template::parse("{1}").render(|_, _| Some("{user}".to_string()));
+        assert_eq!(output, "{user}");
+    }
+
+    #[test]
+    fn reports_used_variables() {
+        let template = Template::parse("{count} deaths {{song}}");
+        assert!(template.uses("count"));
+        assert!(!template.uses("song"));
+    }
+
+    #[test]
+    fn random_stays_in_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let value: i64 = builtin("random", Some("5-10"), &mut rng)
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((5..=10).contains(&value));
+        }
+    }
+
+    #[test]
+    fn random_accepts_reversed_and_default_ranges() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let value: i64 = builtin("random", Some("10-5"), &mut rng)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((5..=10).contains(&value));
+        assert!(builtin("random", None, &mut rng).is_some());
+        assert!(builtin("random", Some("abc"), &mut rng).is_none());
+    }
+
+    #[test]
+    fn parses_negative_ranges() {
+        assert_eq!(parse_range("-5-10"), Some((-5, 10)));
+        assert_eq!(parse_range("-10 - -5"), Some((-10, -5)));
+        assert_eq!(parse_range("5-10"), Some((5, 10)));
+        assert_eq!(parse_range("-5"), None);
+    }
+
+    #[test]
+    fn pick_chooses_an_option() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let value = builtin("pick", Some("a | b | c"), &mut rng).unwrap();
+            assert!(["a", "b", "c"].contains(&value.as_str()));
+        }
+        assert!(builtin("pick", Some(" | "), &mut rng).is_none());
+        assert!(builtin("pick", None, &mut rng).is_none());
+    }
+
+    #[test]
+    fn builtin_ignores_other_names() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(builtin("user", None, &mut rng).is_none());
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::{
+    commands::CustomCommands,
+    config::{Config, TimerConfig},
+    helix::HelixClient,
+};
+
+const TICK: Duration = Duration::from_secs(15);
+
+struct Timer {
+    config: TimerConfig,
+    enabled: bool,
+    last_sent: Instant,
+    messages_at_last: u64,
+}
+
+#[derive(Clone)]
+pub struct Timers {
+    custom_commands: CustomCommands,
+    helix: HelixClient,
+    timers: Arc<Mutex<HashMap<String, Timer>>>,
+    messages: Arc<AtomicU64>,
+}
+
+impl Timers {
+    pub fn new(config: Arc<Config>, custom_commands: CustomCommands, helix: HelixClient) -> Self {
+        let now = Instant::now();
+        let timers = config
+            .timers
+            .iter()
+            .map(|(name, timer)| {
+                (
+                    name.clone(),
+                    Timer {
+                        config: timer.clone(),
+                        enabled: timer.enabled,
+                        last_sent: now,
+                        messages_at_last: 0,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            custom_commands,
+            helix,
+            timers: Arc::new(Mutex::new(timers)),
+            messages: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn record_message(&self) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn spawn(&self, outbox: mpsc::UnboundedSender<String>) {
+        if self.timers.lock().unwrap().is_empty() {
+            return;
+        }
+
+        let timers = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK);
+            loop {
+                interval.tick().await;
+                for (name, timer) in timers.due() {
+                    let Some(message) = timers.resolve(&timer).await else {
+                        warn!("Timer {} has no message or unknown command", name);
+                        continue;
+                    };
+                    debug!("Timer {} fired", name);
+                    if timer.announce {
+                        match timers.helix.send_announcement(&message).await {
+                            Ok(()) => continue,
+                            Err(e) => warn!("Timer {} announcement failed: {}", name, e),
+                        }
+                    }
+                    if outbox.send(message).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    fn due(&self) -> Vec<(String, TimerConfig)> {
+        let now = Instant::now();
+        let messages = self.messages.load(Ordering::Relaxed);
+        let mut due = Vec::new();
+
+        for (name, timer) in self.timers.lock().unwrap().iter_mut() {
+            let interval = Duration::from_secs(timer.config.interval * 60);
+            let chatter = messages - timer.messages_at_last;
+
+            if !timer.enabled
+                || now - timer.last_sent < interval
+                || chatter < timer.config.min_messages
+            {
+                continue;
+            }
+
+            timer.last_sent = now;
+            timer.messages_at_last = messages;
+
+            due.push((name.clone(), timer.config.clone()));
+        }
+
+        due
+    }
+
+    async fn resolve(&self, timer: &TimerConfig) -> Option<String> {
+        if let Some(message) = &timer.message {
+            return Some(message.clone());
+        }
+
+        let command = timer.command.as_ref()?;
+        self.custom_commands.render(command).await
+    }
+
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> bool {
+        match self.timers.lock().unwrap().get_mut(name) {
+            Some(timer) => {
+                timer.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<(String, bool, u64)> {
+        let mut timers: Vec<_> = self
+            .timers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, timer)| (name.clone(), timer.enabled, timer.config.interval))
+            .collect();
+        timers.sort();
+        timers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::{SpotifyService, TemplateContext},
+        counters::CounterStore,
+        helix::HelixClient,
+        storage::MemoryStorage,
+    };
+
+    fn timers() -> Timers {
+        let config: Arc<Config> = Arc::new(
+            toml::from_str(
+                r#"
+                [twitch]
+                username = "bot"
+                channel = "april"
+                oauth_token = "token"
+
+                [spotify]
+                client_id = "id"
+                client_secret = "secret"
+                refresh_token = "refresh"
+                reward_id = "reward"
+
+                [commands.simple]
+                discord = "join {channel}'s discord, {user} #{count}"
+
+                [timers.discord]
+                interval = 0
+                command = "discord"
+                "#,
+            )
+            .unwrap(),
+        );
+        let storage: crate::storage::SharedStorage = Arc::new(MemoryStorage::default());
+        let counters = CounterStore::new(&config.counters, Arc::clone(&storage));
+        let context = TemplateContext {
+            config: Arc::clone(&config),
+            helix: HelixClient::new(Arc::clone(&config)),
+            spotify: SpotifyService::new(Arc::clone(&config)),
+            counters,
+        };
+        let helix = context.helix.clone();
+        let custom_commands = CustomCommands::load(&config, Default::default(), storage, context);
+        Timers::new(config, custom_commands, helix)
+    }
+
+    #[tokio::test]
+    async fn renders_templated_commands() {
+        let timers = timers();
+        let due = timers.due();
+        assert_eq!(due.len(), 1);
+
+        let (name, timer) = &due[0];
+        assert_eq!(name, "discord");
+        assert_eq!(
+            timers.resolve(timer).await.as_deref(),
+            Some("join april's discord, april #1")
+        );
+    }
+}